@@ -1,7 +1,10 @@
 use num_bigint::BigUint;
-use num_traits::{FromPrimitive, One, Zero};
+use num_traits::{One, Zero};
 
-use super::traits::Integral;
+use super::{
+    primality::baillie_psw,
+    traits::{ConditionalSwap, Field, Integral},
+};
 
 /// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b).
 pub fn extended_gcd<T: Integral>(a: T, b: T) -> (T, T, T) {
@@ -41,20 +44,98 @@ where
     result
 }
 
+/// `app(identity, value x cnt)` computed with the Montgomery ladder.
+///
+/// Performs exactly `bits` steps of one `app` on a sum and one on a double,
+/// without branching on the bits of `cnt` or indexing by them: the two
+/// accumulators are exchanged with [`ConditionalSwap`] instead. It is only as
+/// constant-time as `app` and the swap of `T`. `cnt` must fit into `bits`
+/// bits.
+pub fn montgomery_ladder<T, F>(
+    app: F,
+    cnt: &BigUint,
+    bits: u64,
+    value: T,
+    identity: T,
+) -> T
+where
+    T: Clone + ConditionalSwap,
+    F: Fn(T, T) -> T,
+{
+    debug_assert!(cnt.bits() <= bits);
+    let (mut result, mut other) = (identity, value);
+    let mut swapped = 0;
+    for i in (0..bits).rev() {
+        let bit = cnt.bit(i) as u64;
+        result.conditional_swap(&mut other, (swapped ^ bit).wrapping_neg());
+        swapped = bit;
+        other = app(result.clone(), other);
+        result = app(result.clone(), result);
+    }
+    result.conditional_swap(&mut other, swapped.wrapping_neg());
+    result
+}
+
+/// Baillie-PSW primality test, exact for every `value` below 2^64.
 pub fn is_prime(value: BigUint) -> bool {
-    if value.is_one() {
-        return false;
+    baillie_psw(&value)
+}
+
+/// Square root of `value` in a prime field of order `p`, if it exists.
+///
+/// `unit` is the one of that field, passed in because residues modulo a
+/// runtime prime cannot create constants on their own. Uses a single
+/// exponentiation for p = 3 mod 4, Atkin's method for p = 5 mod 8 and
+/// Tonelli-Shanks for the remaining p = 1 mod 8.
+pub fn sqrt_mod<F: Field>(value: F, unit: F, p: &BigUint) -> Option<F> {
+    if value.is_zero() {
+        return Some(value);
     }
-    let mut x = BigUint::from_i8(2).unwrap();
-    loop {
-        if &x * &x > value {
-            break true;
+    let one = BigUint::one();
+    if !value.clone().pow(p >> 1).is_one() {
+        return None;
+    }
+    let root = match p.iter_u64_digits().next().unwrap_or(0) & 7 {
+        3 | 7 => value.clone().pow((p + &one) >> 2),
+        5 => {
+            let double = value.clone() + value.clone();
+            let b = double.clone().pow(p >> 3);
+            let i = double * b.clone() * b.clone();
+            value.clone() * b * (i - unit)
         }
-        if (&value % &x).is_zero() {
-            return false;
+        _ => tonelli_shanks(value.clone(), unit, p),
+    };
+    debug_assert!(root.clone() * root.clone() == value);
+    Some(root)
+}
+
+/// Tonelli-Shanks for a quadratic residue `value` modulo an odd prime `p`.
+fn tonelli_shanks<F: Field>(value: F, unit: F, p: &BigUint) -> F {
+    let one = BigUint::one();
+    let s = (p - &one).trailing_zeros().unwrap();
+    let q = (p - &one) >> s;
+    let mut z = unit.clone() + unit.clone();
+    while z.clone().pow(p >> 1).is_one() {
+        z = z + unit.clone();
+    }
+    let mut c = z.pow(q.clone());
+    let mut x = value.clone().pow((&q + &one) >> 1);
+    let mut t = value.pow(q);
+    let mut m = s;
+    while !t.is_one() {
+        let mut i = 0;
+        let mut square = t.clone();
+        while !square.is_one() {
+            square = square.clone() * square;
+            i += 1;
         }
-        x += BigUint::one();
+        let b = (0..m - i - 1).fold(c, |b, _| b.clone() * b);
+        x = x * b.clone();
+        c = b.clone() * b;
+        t = t * c.clone();
+        m = i;
     }
+    x
 }
 
 #[cfg(test)]
@@ -63,7 +144,11 @@ mod tests {
     use num_traits::{One, Zero};
 
     use super::replace;
-    use std::ops::Add;
+    use crate::algebra::{
+        fields::{dynamic::DynZn, montgomery::MontgomeryContext},
+        traits::ConditionalSwap,
+    };
+    use std::{ops::Add, rc::Rc};
 
     #[test]
     fn repeat_monoid() {
@@ -81,6 +166,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn montgomery_ladder() {
+        for n in 0..(1usize << 14) {
+            let n = BigUint::from(n);
+            assert_eq!(
+                super::montgomery_ladder(
+                    BigUint::add,
+                    &n,
+                    16,
+                    BigUint::one(),
+                    BigUint::zero()
+                ),
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn conditional_swap() {
+        let (a, b) = (BigInt::from(-5), BigInt::from(1u128 << 100));
+        let (mut x, mut y) = (a.clone(), b.clone());
+        x.conditional_swap(&mut y, 0);
+        assert_eq!((&x, &y), (&a, &b));
+        x.conditional_swap(&mut y, u64::MAX);
+        assert_eq!((&x, &y), (&b, &a));
+    }
+
+    #[test]
+    fn sqrt_mod() {
+        for p in [10007u32, 10009, 10037] {
+            let context = Rc::new(MontgomeryContext::new(BigUint::from(p)));
+            let residue = |x: u32| DynZn::new(BigUint::from(x), &context);
+            let mut residues = 0;
+            for x in 0..1000 {
+                let x = residue(x);
+                let root = super::sqrt_mod(x.clone(), residue(1), &p.into());
+                if let Some(root) = root {
+                    assert!(root.clone() * root == x);
+                    residues += 1;
+                }
+                let square = x.clone() * x;
+                let root = super::sqrt_mod(square, residue(1), &p.into());
+                assert!(root.is_some());
+            }
+            assert!(residues > 0 && residues < 1000);
+        }
+    }
+
     fn gcd(mut a: BigInt, mut b: BigInt) -> BigInt {
         while !b.is_zero() {
             a = replace(a % b.clone(), &mut b);
@@ -0,0 +1,286 @@
+use std::{
+    convert::TryInto,
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+
+use super::{
+    algo::repeat_monoid,
+    curve::NotOnCurve,
+    traits::{ConditionalSwap, Field, FinGroup, Group},
+};
+
+/// Non-supersingular curve `y^2 + x * y = x^3 + a * x^2 + b` over a field
+/// of characteristic 2.
+pub trait BinaryCurve<F: Field>: Sized {
+    fn group_order() -> BigUint;
+
+    /// Number of points on the curve divided by `group_order`.
+    fn cofactor() -> BigUint {
+        BigUint::one()
+    }
+
+    fn a() -> F;
+    fn b() -> F;
+
+    fn affine(x: F, y: F) -> Result<BinaryPoint<F, Self>, NotOnCurve> {
+        let lhs = y.clone() * y.clone() + x.clone() * y.clone();
+        let xx = x.clone() * x.clone();
+        if lhs == xx.clone() * x.clone() + Self::a() * xx + Self::b() {
+            Ok(BinaryPoint::affine(x, y))
+        } else {
+            Err(NotOnCurve)
+        }
+    }
+}
+
+/// Point in affine coordinates, `None` being the point at infinity.
+#[derive(Debug)]
+pub struct BinaryPoint<F, C> {
+    coords: Option<(F, F)>,
+    curve: PhantomData<C>,
+}
+
+impl<F, C> BinaryPoint<F, C> {
+    fn from_coords(coords: Option<(F, F)>) -> Self {
+        Self {
+            coords,
+            curve: PhantomData,
+        }
+    }
+
+    fn affine(x: F, y: F) -> Self {
+        Self::from_coords(Some((x, y)))
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> BinaryPoint<F, C> {
+    /// Line through `(x1, y1)` and `(x2, y2)` with slope `lambda`.
+    fn chord(lambda: F, (x1, y1): (F, F), x2: F) -> Self {
+        let x3 =
+            lambda.clone() * lambda.clone() + lambda.clone() + x1.clone() + x2
+                - C::a();
+        let y3 = lambda * (x1 + x3.clone()) + x3.clone() + y1;
+        Self::affine(x3, y3)
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> From<BinaryPoint<F, C>> for Option<(F, F)> {
+    fn from(point: BinaryPoint<F, C>) -> Self {
+        point.coords
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Group for BinaryPoint<F, C> {
+    fn double(self) -> Self {
+        match self.coords {
+            Some((x, y)) if !x.is_zero() => {
+                let lambda = x.clone() + y.clone() / x.clone();
+                let x3 =
+                    lambda.clone() * lambda.clone() + lambda.clone() - C::a();
+                let y3 = x.clone() * x + (lambda + F::one()) * x3.clone();
+                Self::affine(x3, y3)
+            }
+            _ => Self::zero(),
+        }
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> FinGroup for BinaryPoint<F, C> {
+    fn order() -> BigUint {
+        C::group_order()
+    }
+}
+
+/// Affine coordinates have no room for the point at infinity, so whether a
+/// point is zero is not hidden.
+impl<F: Field, C> ConditionalSwap for BinaryPoint<F, C> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let split = |point: &mut Self| match point.coords.take() {
+            Some((x, y)) => ([1], x, y),
+            None => ([0], F::zero(), F::zero()),
+        };
+        let (mut a, mut b) = (split(self), split(other));
+        a.conditional_swap(&mut b, mask);
+        for (point, (is_affine, x, y)) in [(self, a), (other, b)] {
+            if is_affine[0] == 1 {
+                point.coords = Some((x, y));
+            }
+        }
+    }
+}
+
+impl<F: Clone, C> Clone for BinaryPoint<F, C> {
+    fn clone(&self) -> Self {
+        Self::from_coords(self.coords.clone())
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> PartialEq for BinaryPoint<F, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coords == other.coords
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Eq for BinaryPoint<F, C> {}
+
+impl<F: Field, C: BinaryCurve<F>> Add for BinaryPoint<F, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let ((x1, y1), (x2, y2)) = match (self.coords, rhs.coords) {
+            (None, coords) | (coords, None) => {
+                return Self::from_coords(coords)
+            }
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+        };
+        if x1 != x2 {
+            let lambda = (y1.clone() + y2) / (x1.clone() + x2.clone());
+            Self::chord(lambda, (x1, y1), x2)
+        } else if y1 == y2 {
+            Self::affine(x1, y1).double()
+        } else {
+            Self::zero()
+        }
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Sub for BinaryPoint<F, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Neg for BinaryPoint<F, C> {
+    type Output = Self;
+
+    /// `-(x, y) = (x, x + y)`.
+    fn neg(self) -> Self::Output {
+        Self::from_coords(self.coords.map(|(x, y)| (x.clone(), x + y)))
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Mul<BigInt> for BinaryPoint<F, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        match rhs.try_into() {
+            Ok(rhs) => repeat_monoid(Self::add, rhs, self, Self::zero()),
+            Err(err) => -self * -err.into_original(),
+        }
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Sum for BinaryPoint<F, C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl<F: Field, C: BinaryCurve<F>> Zero for BinaryPoint<F, C> {
+    fn zero() -> Self {
+        Self::from_coords(None)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coords.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+
+    use super::{BinaryCurve, BinaryPoint};
+    use crate::algebra::{
+        fields::binary::{BinaryPoly, Gf2m},
+        traits::{FinGroup, Group},
+    };
+
+    /// `x^5 + x^2 + 1`.
+    struct F32;
+
+    impl BinaryPoly<1> for F32 {
+        const DEGREE: usize = 5;
+        const TERMS: &'static [usize] = &[2, 0];
+    }
+
+    type F = Gf2m<F32, 1>;
+
+    /// `y^2 + x * y = x^3 + x^2 + 1` with 22 points.
+    struct Toy;
+
+    impl BinaryCurve<F> for Toy {
+        fn group_order() -> BigUint {
+            BigUint::from(11u32)
+        }
+
+        fn cofactor() -> BigUint {
+            BigUint::from(2u32)
+        }
+
+        fn a() -> F {
+            F::from(1)
+        }
+
+        fn b() -> F {
+            F::from(1)
+        }
+    }
+
+    fn points() -> Vec<BinaryPoint<F, Toy>> {
+        let mut points = vec![BinaryPoint::zero()];
+        for x in 0..32 {
+            for y in 0..32 {
+                if let Ok(point) = Toy::affine(F::from(x), F::from(y)) {
+                    points.push(point);
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn group_laws() {
+        let points = points();
+        assert_eq!(points.len(), 22);
+        for p in &points {
+            assert!((p.clone() * BigInt::from(22)).is_zero());
+            assert!(p.clone().double() == p.clone() + p.clone());
+            assert!((p.clone() - p.clone()).is_zero());
+            for q in points.iter().step_by(3) {
+                let sum = p.clone() + q.clone();
+                assert!(sum == q.clone() + p.clone());
+                assert!(points.contains(&sum));
+                for r in points.iter().step_by(5) {
+                    assert!(
+                        (sum.clone() + r.clone())
+                            == p.clone() + (q.clone() + r.clone())
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn subgroup() {
+        let points = points();
+        let cofactor = BigInt::from(Toy::cofactor());
+        for point in points {
+            let point = point * cofactor.clone();
+            let order = BinaryPoint::<F, Toy>::order();
+            assert!((point.clone() * BigInt::from(order)).is_zero());
+            for k in 0..11 {
+                let k = BigInt::from(k);
+                assert!(point.clone().mul_secret(&k) == point.clone() * k);
+            }
+        }
+    }
+}
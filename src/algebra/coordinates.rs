@@ -0,0 +1,510 @@
+//! Point arithmetic on `y^2 = x^3 + ax + b` in several coordinate systems.
+//!
+//! Formulas follow the Explicit-Formulas Database and
+//! Renes–Costello–Batina, "Complete addition formulas for prime order
+//! elliptic curves" (2015). All functions expect finite points unless stated
+//! otherwise.
+
+use super::traits::Field;
+
+pub type Coords<F> = (F, F, F);
+
+/// Coordinate system together with the formulas used on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Model {
+    /// `(X : Y : Z)` for `(X / Z, Y / Z)`, infinity is `(0 : 1 : 0)`.
+    Homogeneous,
+    /// `(X : Y : Z)` for `(X / Z^2, Y / Z^3)`, infinity has `Z = 0`.
+    Jacobian,
+    /// Homogeneous coordinates with exception-free formulas, which handle
+    /// doubling and infinity without branching.
+    Complete,
+}
+
+impl Model {
+    pub fn zero<F: Field>(self) -> Coords<F> {
+        match self {
+            Model::Homogeneous | Model::Complete => {
+                (F::zero(), F::one(), F::zero())
+            }
+            Model::Jacobian => (F::one(), F::one(), F::zero()),
+        }
+    }
+
+    pub fn to_affine<F: Field>(self, (x, y, z): Coords<F>) -> (F, F) {
+        let i = z.inv();
+        match self {
+            Model::Homogeneous | Model::Complete => (x * i.clone(), y * i),
+            Model::Jacobian => {
+                let i2 = sq(i.clone());
+                (x * i2.clone(), y * i2 * i)
+            }
+        }
+    }
+
+    /// Converts to homogeneous coordinates, keeping infinity valid for
+    /// complete formulas.
+    pub fn to_homogeneous<F: Field>(self, (x, y, z): Coords<F>) -> Coords<F> {
+        match self {
+            _ if z.is_zero() => Model::Homogeneous.zero(),
+            Model::Homogeneous | Model::Complete => (x, y, z),
+            Model::Jacobian => (x * z.clone(), y, cube(z)),
+        }
+    }
+
+    /// Inverse of [`Model::to_homogeneous`].
+    pub fn from_homogeneous<F: Field>(self, (x, y, z): Coords<F>) -> Coords<F> {
+        match self {
+            _ if z.is_zero() => self.zero(),
+            Model::Homogeneous | Model::Complete => (x, y, z),
+            Model::Jacobian => (x * z.clone(), y * sq(z.clone()), z),
+        }
+    }
+
+    /// Checks equality of two finite points.
+    pub fn eq<F: Field>(self, lhs: &Coords<F>, rhs: &Coords<F>) -> bool {
+        let (x1, y1, z1) = lhs.clone();
+        let (x2, y2, z2) = rhs.clone();
+        match self {
+            Model::Homogeneous | Model::Complete => {
+                x1 * z2.clone() == x2 * z1.clone() && y1 * z2 == y2 * z1
+            }
+            Model::Jacobian => {
+                let (z1z1, z2z2) = (sq(z1.clone()), sq(z2.clone()));
+                x1 * z2z2.clone() == x2 * z1z1.clone()
+                    && y1 * z2z2 * z2 == y2 * z1z1 * z1
+            }
+        }
+    }
+}
+
+pub fn homogeneous_add<F: Field>(
+    p: Coords<F>,
+    q: Coords<F>,
+    a: F,
+) -> Coords<F> {
+    let (x1, y1, z1) = p;
+    let (x2, y2, z2) = q;
+    let u = y2 * z1.clone() - y1.clone() * z2.clone();
+    let v = x2 * z1.clone() - x1.clone() * z2.clone();
+    if v.is_zero() {
+        return if u.is_zero() {
+            homogeneous_double((x1, y1, z1), a)
+        } else {
+            Model::Homogeneous.zero()
+        };
+    }
+    let vv = sq(v.clone());
+    let vvv = vv.clone() * v.clone();
+    let z1z2 = z1 * z2.clone();
+    let r = vv * x1 * z2.clone();
+    let w = sq(u.clone()) * z1z2.clone() - vvv.clone() - dbl(r.clone());
+
+    let x = v * w.clone();
+    let y = u * (r - w) - vvv.clone() * y1 * z2;
+    let z = vvv * z1z2;
+    (x, y, z)
+}
+
+pub fn homogeneous_double<F: Field>((x, y, z): Coords<F>, a: F) -> Coords<F> {
+    if y.is_zero() {
+        return Model::Homogeneous.zero();
+    }
+    let w = a * sq(z.clone()) + triple(sq(x.clone()));
+    let s = y.clone() * z;
+    let b = x * y.clone() * s.clone();
+    let h = sq(w.clone()) - dbl(dbl(dbl(b.clone())));
+
+    let x = dbl(h.clone() * s.clone());
+    let y = w * (dbl(dbl(b)) - h) - dbl(dbl(dbl(sq(y * s.clone()))));
+    let z = dbl(dbl(dbl(cube(s))));
+    (x, y, z)
+}
+
+/// `add-2007-bl`, falls back to doubling when both points coincide.
+pub fn jacobian_add<F: Field>(
+    p: Coords<F>,
+    q: Coords<F>,
+    a: F,
+    a_is_minus_three: bool,
+) -> Coords<F> {
+    let (x1, y1, z1) = p;
+    let (x2, y2, z2) = q;
+    let z1z1 = sq(z1.clone());
+    let z2z2 = sq(z2.clone());
+    let u1 = x1.clone() * z2z2.clone();
+    let u2 = x2 * z1z1.clone();
+    let s1 = y1.clone() * z2.clone() * z2z2.clone();
+    let s2 = y2 * z1.clone() * z1z1.clone();
+    let h = u2 - u1.clone();
+    let r = dbl(s2 - s1.clone());
+    if h.is_zero() {
+        return if r.is_zero() {
+            jacobian_double((x1, y1, z1), a, a_is_minus_three)
+        } else {
+            Model::Jacobian.zero()
+        };
+    }
+    let i = sq(dbl(h.clone()));
+    let j = h.clone() * i.clone();
+    let v = u1 * i;
+
+    let x = sq(r.clone()) - j.clone() - dbl(v.clone());
+    let y = r * (v - x.clone()) - dbl(s1 * j);
+    let z = (sq(z1 + z2) - z1z1 - z2z2) * h;
+    (x, y, z)
+}
+
+/// `madd-2007-bl`: addition of a finite point with an affine one.
+pub fn jacobian_add_affine<F: Field>(
+    p: Coords<F>,
+    (x2, y2): (F, F),
+    a: F,
+    a_is_minus_three: bool,
+) -> Coords<F> {
+    let (x1, y1, z1) = p;
+    let z1z1 = sq(z1.clone());
+    let u2 = x2 * z1z1.clone();
+    let s2 = y2 * z1.clone() * z1z1.clone();
+    let h = u2 - x1.clone();
+    let r = dbl(s2 - y1.clone());
+    if h.is_zero() {
+        return if r.is_zero() {
+            jacobian_double((x1, y1, z1), a, a_is_minus_three)
+        } else {
+            Model::Jacobian.zero()
+        };
+    }
+    let hh = sq(h.clone());
+    let i = dbl(dbl(hh.clone()));
+    let j = h.clone() * i.clone();
+    let v = x1 * i;
+
+    let x = sq(r.clone()) - j.clone() - dbl(v.clone());
+    let y = r * (v - x.clone()) - dbl(y1 * j);
+    let z = sq(z1 + h) - z1z1 - hh;
+    (x, y, z)
+}
+
+/// `dbl-2001-b` when `a = -3`, `dbl-2007-bl` otherwise.
+pub fn jacobian_double<F: Field>(
+    (x, y, z): Coords<F>,
+    a: F,
+    a_is_minus_three: bool,
+) -> Coords<F> {
+    if a_is_minus_three {
+        let delta = sq(z.clone());
+        let gamma = sq(y.clone());
+        let beta = x.clone() * gamma.clone();
+        let alpha = triple((x.clone() - delta.clone()) * (x + delta.clone()));
+
+        let x = sq(alpha.clone()) - dbl(dbl(dbl(beta.clone())));
+        let z = sq(y + z) - gamma.clone() - delta;
+        let y = alpha * (dbl(dbl(beta)) - x.clone()) - dbl(dbl(dbl(sq(gamma))));
+        (x, y, z)
+    } else {
+        let xx = sq(x.clone());
+        let yy = sq(y.clone());
+        let yyyy = sq(yy.clone());
+        let zz = sq(z.clone());
+        let s = dbl(sq(x + yy.clone()) - xx.clone() - yyyy.clone());
+        let m = if a.is_zero() {
+            triple(xx)
+        } else {
+            triple(xx) + a * sq(zz.clone())
+        };
+        let t = sq(m.clone()) - dbl(s.clone());
+
+        let y3 = m * (s - t.clone()) - dbl(dbl(dbl(yyyy)));
+        let z3 = sq(y + z) - yy - zz;
+        (t, y3, z3)
+    }
+}
+
+/// Algorithm 1 of Renes–Costello–Batina, valid for all inputs including
+/// doubling and infinity.
+pub fn complete_add<F: Field>(
+    p: Coords<F>,
+    q: Coords<F>,
+    a: F,
+    b: F,
+) -> Coords<F> {
+    let (x1, y1, z1) = p;
+    let (x2, y2, z2) = q;
+    let b3 = triple(b);
+    let t0 = x1.clone() * x2.clone();
+    let t1 = y1.clone() * y2.clone();
+    let t2 = z1.clone() * z2.clone();
+    let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone())
+        - (t0.clone() + t1.clone());
+    let t4 = (x1 + z1.clone()) * (x2 + z2.clone()) - (t0.clone() + t2.clone());
+    let t5 = (y1 + z1) * (y2 + z2) - (t1.clone() + t2.clone());
+    let z3 = a.clone() * t4.clone() + b3.clone() * t2.clone();
+    let x3 = t1.clone() - z3.clone();
+    let z3 = t1 + z3;
+    let y3 = x3.clone() * z3.clone();
+    let t1 = triple(t0.clone());
+    let t2 = a.clone() * t2;
+    let t4 = b3 * t4;
+    let t1 = t1 + t2.clone();
+    let t2 = a * (t0 - t2);
+    let t4 = t4 + t2;
+    let y3 = y3 + t1.clone() * t4.clone();
+    let x3 = t3.clone() * x3 - t5.clone() * t4;
+    let z3 = t5 * z3 + t3 * t1;
+    (x3, y3, z3)
+}
+
+/// Algorithm 4 of Renes–Costello–Batina: [`complete_add`] for `a = -3`.
+pub fn complete_add_minus_three<F: Field>(
+    p: Coords<F>,
+    q: Coords<F>,
+    b: F,
+) -> Coords<F> {
+    let (x1, y1, z1) = p;
+    let (x2, y2, z2) = q;
+    let t0 = x1.clone() * x2.clone();
+    let t1 = y1.clone() * y2.clone();
+    let t2 = z1.clone() * z2.clone();
+    let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone())
+        - (t0.clone() + t1.clone());
+    let t4 = (y1 + z1.clone()) * (y2 + z2.clone()) - (t1.clone() + t2.clone());
+    let y3 = (x1 + z1) * (x2 + z2) - (t0.clone() + t2.clone());
+    let x3 = y3.clone() - b.clone() * t2.clone();
+    let x3 = triple(x3);
+    let z3 = t1.clone() - x3.clone();
+    let x3 = t1 + x3;
+    let t2 = triple(t2);
+    let y3 = b * y3 - t2.clone() - t0.clone();
+    let y3 = triple(y3);
+    let t0 = triple(t0) - t2;
+    let t1 = t4.clone() * y3.clone();
+    let t2 = t0.clone() * y3;
+    let y3 = x3.clone() * z3.clone() + t2;
+    let x3 = t3.clone() * x3 - t1;
+    let z3 = t4 * z3 + t3 * t0;
+    (x3, y3, z3)
+}
+
+fn dbl<F: Field>(x: F) -> F {
+    x.clone() + x
+}
+
+fn triple<F: Field>(x: F) -> F {
+    x.clone() + x.clone() + x
+}
+
+fn sq<F: Field>(x: F) -> F {
+    x.clone() * x
+}
+
+fn cube<F: Field>(x: F) -> F {
+    sq(x.clone()) * x
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+    use rand::{thread_rng, Rng};
+
+    use super::Model;
+    use crate::{
+        algebra::{
+            curve::{Curve, EllipticPoint},
+            fields::zn::{BigPrime, Zn},
+            traits::Field,
+        },
+        ecges::{generator, P224, Z224},
+    };
+
+    fn convert<F, C1, C2>(point: EllipticPoint<F, C1>) -> EllipticPoint<F, C2>
+    where
+        F: Field,
+        C1: Curve<F>,
+        C2: Curve<F>,
+    {
+        match Option::from(point) {
+            Some((x, y)) => C2::affine(x, y).unwrap(),
+            None => EllipticPoint::zero(),
+        }
+    }
+
+    fn affine<C: Curve<F>>(point: EllipticPoint<F, C>) -> Option<(F, F)> {
+        let affine = Option::from(point);
+        if let Some((x, y)) = affine.clone() {
+            assert!(C::affine(x, y).is_ok());
+        }
+        affine
+    }
+
+    fn agree<C1: Curve<F>, C2: Curve<F>>() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let (p, q): (EllipticPoint<F, C1>, EllipticPoint<F, C1>) =
+                (rng.gen(), rng.gen());
+            let (p2, q2) =
+                (convert::<F, C1, C2>(p.clone()), convert(q.clone()));
+            assert_eq!(
+                affine(p.clone() + q.clone()),
+                affine(p2.clone() + q2.clone())
+            );
+            assert_eq!(affine(p.clone().double()), affine(p2.clone().double()));
+            assert_eq!(
+                affine(p.clone() + p.clone()),
+                affine(p2.clone().double())
+            );
+            assert!((p2.clone() - p2.clone()).is_zero());
+            assert!(p2.clone() + EllipticPoint::zero() == p2);
+            let q_affine = Option::from(q.clone()).unwrap();
+            assert_eq!(
+                affine(p.clone() + q),
+                affine(p2.clone().add_affine(q_affine))
+            );
+            let k = BigInt::from(rng.gen_range(0..20000));
+            assert_eq!(affine(p * k.clone()), affine(p2 * k));
+        }
+    }
+
+    #[test]
+    fn models_agree() {
+        agree::<Homogeneous, Jacobian>();
+        agree::<Homogeneous, Complete>();
+        agree::<MinusThree<0>, MinusThree<1>>();
+        agree::<MinusThree<0>, MinusThree<2>>();
+    }
+
+    fn order<C: Curve<F>>() {
+        let n = BigInt::from(C::group_order());
+        for _ in 0..10 {
+            let p: EllipticPoint<F, C> = thread_rng().gen();
+            assert!((p * n.clone()).is_zero());
+        }
+    }
+
+    #[test]
+    fn orders() {
+        order::<Homogeneous>();
+        order::<MinusThree<0>>();
+    }
+
+    #[test]
+    fn p224_models_agree() {
+        let k = BigInt::from(rng_scalar());
+        let jacobian = generator();
+        let homogeneous: EllipticPoint<Z224, P224Homogeneous> =
+            convert(jacobian.clone());
+        let complete: EllipticPoint<Z224, P224Complete> =
+            convert(jacobian.clone());
+        let expected = Option::<(Z224, Z224)>::from(jacobian * k.clone());
+        assert!(Option::from(homogeneous * k.clone()) == expected);
+        assert!(Option::from(complete * k) == expected);
+    }
+
+    fn rng_scalar() -> BigUint {
+        BigUint::from(thread_rng().gen::<u128>())
+    }
+
+    type F = Zn<P10007>;
+
+    #[derive(Debug)]
+    pub struct P10007;
+
+    impl BigPrime for P10007 {
+        fn value() -> BigUint {
+            BigUint::from(10007usize)
+        }
+    }
+
+    /// `y^2 = x^3 + 2x + 6` of odd order in every model.
+    pub struct Homogeneous;
+    pub struct Jacobian;
+    pub struct Complete;
+    /// `y^2 = x^3 - 3x + 5` of odd order with models numbered as in [`model`].
+    pub struct MinusThree<const M: usize>;
+
+    fn model(index: usize) -> Model {
+        [Model::Homogeneous, Model::Jacobian, Model::Complete][index]
+    }
+
+    macro_rules! small_curve {
+        ($curve:ty, $model:expr) => {
+            impl Curve<F> for $curve {
+                fn group_order() -> BigUint {
+                    BigUint::from(9861usize)
+                }
+
+                fn a() -> F {
+                    F::from(2)
+                }
+
+                fn b() -> F {
+                    F::from(6)
+                }
+
+                fn model() -> Model {
+                    $model
+                }
+            }
+        };
+    }
+
+    small_curve!(Homogeneous, Model::Homogeneous);
+    small_curve!(Jacobian, Model::Jacobian);
+    small_curve!(Complete, Model::Complete);
+
+    impl<const M: usize> Curve<F> for MinusThree<M> {
+        const A_IS_MINUS_THREE: bool = true;
+
+        fn group_order() -> BigUint {
+            BigUint::from(9957usize)
+        }
+
+        fn a() -> F {
+            -F::from(3)
+        }
+
+        fn b() -> F {
+            F::from(5)
+        }
+
+        fn model() -> Model {
+            model(M)
+        }
+    }
+
+    pub struct P224Homogeneous;
+    pub struct P224Complete;
+
+    impl Curve<Z224> for P224Homogeneous {
+        fn group_order() -> BigUint {
+            <P224 as Curve<Z224>>::group_order()
+        }
+
+        fn a() -> Z224 {
+            <P224 as Curve<Z224>>::a()
+        }
+
+        fn b() -> Z224 {
+            <P224 as Curve<Z224>>::b()
+        }
+    }
+
+    impl Curve<Z224> for P224Complete {
+        fn group_order() -> BigUint {
+            <P224 as Curve<Z224>>::group_order()
+        }
+
+        fn a() -> Z224 {
+            <P224 as Curve<Z224>>::a()
+        }
+
+        fn b() -> Z224 {
+            <P224 as Curve<Z224>>::b()
+        }
+
+        fn model() -> Model {
+            Model::Complete
+        }
+    }
+}
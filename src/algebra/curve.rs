@@ -1,5 +1,4 @@
 use std::{
-    convert::TryInto,
     error::Error,
     fmt::Debug,
     iter::Sum,
@@ -8,22 +7,39 @@ use std::{
 };
 
 use num_bigint::{BigInt, BigUint};
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use rand::{distributions::Standard, prelude::Distribution, Rng};
 use thiserror::Error;
 
 use crate::bytes::{Decoding, Deserialize, Encoding, Serialize};
 
 use super::{
-    algo::repeat_monoid,
-    traits::{Field, FinGroup, Group, Sqrt},
+    algo::montgomery_ladder,
+    coordinates::{self, Model},
+    scalar::{self, FixedBaseTable},
+    sec1::{self, Sec1Error, Sec1Point},
+    traits::{reduce_scalar, ConditionalSwap, Field, FinGroup, Group, Sqrt},
 };
 
 pub trait Curve<F: Field>: Sized {
+    /// Enables cheaper doubling, set when `a` is known to be `-3`.
+    const A_IS_MINUS_THREE: bool = false;
+
     fn group_order() -> BigUint;
+
+    /// Number of points on the curve divided by `group_order`.
+    fn cofactor() -> BigUint {
+        BigUint::one()
+    }
+
     fn a() -> F;
     fn b() -> F;
 
+    /// Coordinates and formulas used for points on this curve.
+    fn model() -> Model {
+        Model::Homogeneous
+    }
+
     fn affine(x: F, y: F) -> Result<EllipticPoint<F, Self>, NotOnCurve> {
         if check_solution::<F, Self>(x.clone(), y.clone()) {
             Ok(EllipticPoint::affine(x, y))
@@ -37,6 +53,8 @@ pub trait Curve<F: Field>: Sized {
     }
 }
 
+/// Short Weierstrass form needs characteristic other than 2 and 3, curves
+/// over binary fields are handled by [`super::binary_curve`].
 pub fn check_char<F: Debug + Field>() {
     assert!(F::one() + F::one() != F::zero());
     assert!(F::one() + F::one() + F::one() != F::zero());
@@ -55,6 +73,112 @@ pub trait Encoder<T> {
     fn decode(item: T) -> Result<Vec<u8>, Self::Error>;
 }
 
+/// Curve with an endomorphism `(x, y) -> (beta * x, y)` that acts as
+/// multiplication by `lambda` on the prime order subgroup.
+pub trait GlvCurve<F: Field>: Curve<F> {
+    fn beta() -> F;
+    fn lambda() -> BigUint;
+
+    /// Reduced basis of the lattice of `(i, j)` with `i + j * lambda = 0`
+    /// modulo the group order, see [`scalar::glv_decompose`].
+    fn basis() -> [(BigInt, BigInt); 2];
+}
+
+/// Curve with a standard generator of its prime order subgroup.
+pub trait NamedCurve<F: Field>: Curve<F> {
+    fn generator() -> EllipticPoint<F, Self>;
+}
+
+/// [`Encoder::encode`] that maps a byte `x` to `x * G`.
+pub fn encode_byte<F: Field, C: NamedCurve<F>>(
+    stream: &mut impl Iterator<Item = u8>,
+) -> Option<EllipticPoint<F, C>> {
+    stream.next().map(|x| C::generator() * BigInt::from(x))
+}
+
+/// Inverse of [`encode_byte`], searches through all 256 multiples.
+pub fn decode_byte<F: Field, C: NamedCurve<F>>(
+    item: EllipticPoint<F, C>,
+) -> Result<Vec<u8>, NotFound> {
+    let generator = C::generator();
+    let mut multiple = EllipticPoint::zero();
+    for x in u8::MIN..=u8::MAX {
+        if item == multiple {
+            return Ok(vec![x]);
+        }
+        multiple = multiple + generator.clone();
+    }
+    Err(NotFound)
+}
+
+#[derive(Debug, Error)]
+#[error("inverse not found, order is too big")]
+pub struct NotFound;
+
+/// [`Encoder::encode`] after Koblitz: the next [`koblitz_chunk_len`] bytes of
+/// the stream go into `x` after their count and before a counter that is
+/// increased until `x` is on the curve.
+pub fn encode_koblitz<F, C>(
+    stream: &mut impl Iterator<Item = u8>,
+) -> Option<EllipticPoint<F, C>>
+where
+    F: Field + FinGroup + Sqrt + From<BigUint>,
+    C: Curve<F>,
+{
+    let len = koblitz_chunk_len(&F::order()) + 2;
+    let chunk: Vec<_> = stream.take(len - 2).collect();
+    if chunk.is_empty() {
+        return None;
+    }
+    let mut bytes = vec![chunk.len() as u8];
+    bytes.extend(chunk);
+    bytes.resize(len, 0);
+    let point = (u8::MIN..=u8::MAX).find_map(|counter| {
+        bytes[len - 1] = counter;
+        let x = F::from(BigUint::from_bytes_be(&bytes));
+        C::solve(x.clone()).map(|y| EllipticPoint::affine(x, y))
+    });
+    Some(point.expect("no point among 256 candidates"))
+}
+
+/// Inverse of [`encode_koblitz`], reads the chunk back from `x`.
+pub fn decode_koblitz<F, C>(
+    item: EllipticPoint<F, C>,
+) -> Result<Vec<u8>, NotEmbedded>
+where
+    F: Field + FinGroup,
+    C: Curve<F>,
+    BigUint: From<F>,
+{
+    let chunk_len = koblitz_chunk_len(&F::order());
+    let (x, _) = Option::<(F, F)>::from(item).ok_or(NotEmbedded)?;
+    let x = BigUint::from(x).to_bytes_be();
+    if x.len() != chunk_len + 2 || x[0] == 0 || x[0] as usize > chunk_len {
+        return Err(NotEmbedded);
+    }
+    Ok(x[1..=x[0] as usize].to_vec())
+}
+
+/// Number of bytes [`encode_koblitz`] packs into one point.
+///
+/// The count byte leads `x`, so it has to stay below the leading byte of the
+/// modulus: that gives `len - 2` bytes for P-224 and other moduli starting
+/// with `0xff`, where `len` is the byte length of the field. Otherwise `x` is
+/// kept one byte shorter than the modulus, e.g. 63 bytes for P-521.
+pub fn koblitz_chunk_len(modulus: &BigUint) -> usize {
+    let len = sec1::field_len(modulus);
+    let top = modulus.to_bytes_be()[0] as usize;
+    if top > len - 2 {
+        len - 2
+    } else {
+        len - 3
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("point does not carry an embedded chunk")]
+pub struct NotEmbedded;
+
 #[derive(Debug, Error)]
 #[error("Point is not on curve.")]
 pub struct NotOnCurve;
@@ -84,6 +208,79 @@ impl<F: Field, C> EllipticPoint<F, C> {
     }
 }
 
+impl<F: Field, C: Curve<F>> EllipticPoint<F, C> {
+    pub fn double(self) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+        let coords = match C::model() {
+            Model::Homogeneous => {
+                coordinates::homogeneous_double(self.coords, C::a())
+            }
+            Model::Jacobian => coordinates::jacobian_double(
+                self.coords,
+                C::a(),
+                C::A_IS_MINUS_THREE,
+            ),
+            Model::Complete => {
+                let coords = self.coords.clone();
+                Self::complete_add(self.coords, coords)
+            }
+        };
+        Self::from_coords(coords)
+    }
+
+    /// Mixed addition with a point given in affine coordinates.
+    pub fn add_affine(self, (x, y): (F, F)) -> Self {
+        match C::model() {
+            Model::Jacobian if !self.is_zero() => {
+                Self::from_coords(coordinates::jacobian_add_affine(
+                    self.coords,
+                    (x, y),
+                    C::a(),
+                    C::A_IS_MINUS_THREE,
+                ))
+            }
+            _ => self + Self::affine(x, y),
+        }
+    }
+
+    /// Multiplication by a public scalar using its width-`width` NAF.
+    pub fn mul_wnaf(self, scalar: &BigInt, width: usize) -> Self {
+        scalar::mul_wnaf(self, scalar, width)
+    }
+
+    /// Table for repeated multiplication of this point by public scalars.
+    pub fn precompute(self, window: usize) -> FixedBaseTable<Self> {
+        FixedBaseTable::new(self, window)
+    }
+
+    fn complete_add(lhs: (F, F, F), rhs: (F, F, F)) -> (F, F, F) {
+        if C::A_IS_MINUS_THREE {
+            coordinates::complete_add_minus_three(lhs, rhs, C::b())
+        } else {
+            coordinates::complete_add(lhs, rhs, C::a(), C::b())
+        }
+    }
+}
+
+impl<F: Field, C: GlvCurve<F>> EllipticPoint<F, C> {
+    /// Multiplication by `lambda` at the cost of one field multiplication.
+    pub fn endomorphism(mut self) -> Self {
+        self.coords.0 = self.coords.0 * C::beta();
+        self
+    }
+
+    /// Multiplication by a public scalar split into two half-length ones,
+    /// which halves the number of doublings.
+    pub fn mul_glv(self, scalar: &BigInt) -> Self {
+        let (k1, k2) =
+            scalar::glv_decompose(scalar, &C::group_order(), &C::basis());
+        let points = [self.clone(), self.endomorphism()];
+        scalar::straus(&points, &[k1, k2], scalar::DEFAULT_WINDOW + 1)
+    }
+}
+
 impl<F: Field + Sqrt, C: Curve<F>> Distribution<EllipticPoint<F, C>>
     for Standard
 where
@@ -99,35 +296,19 @@ where
     }
 }
 
-impl<F: Clone, C> EllipticPoint<F, C> {
-    fn x(&self) -> F {
-        self.coords.0.clone()
-    }
-
-    fn y(&self) -> F {
-        self.coords.1.clone()
-    }
-
-    fn z(&self) -> F {
-        self.coords.2.clone()
-    }
-}
-
 impl<F: Field, C> EllipticPoint<F, C> {
     fn is_infinite(&self) -> bool {
         self.coords.2.is_zero()
     }
 }
 
-impl<F: Field, C> From<EllipticPoint<F, C>> for (F, F) {
+impl<F: Field, C: Curve<F>> From<EllipticPoint<F, C>> for (F, F) {
     fn from(point: EllipticPoint<F, C>) -> Self {
-        let (x, y, z) = point.coords;
-        let i = z.inv();
-        (x * i.clone(), y * i)
+        C::model().to_affine(point.coords)
     }
 }
 
-impl<F: Field, C> From<EllipticPoint<F, C>> for Option<(F, F)> {
+impl<F: Field, C: Curve<F>> From<EllipticPoint<F, C>> for Option<(F, F)> {
     fn from(point: EllipticPoint<F, C>) -> Self {
         if point.is_infinite() {
             None
@@ -137,12 +318,46 @@ impl<F: Field, C> From<EllipticPoint<F, C>> for Option<(F, F)> {
     }
 }
 
-impl<F: Field, C: Curve<F>> Group for EllipticPoint<F, C> {}
+impl<F: Field, C: Curve<F>> Group for EllipticPoint<F, C> {
+    fn double(self) -> Self {
+        EllipticPoint::double(self)
+    }
+}
 
 impl<F: Field, C: Curve<F>> FinGroup for EllipticPoint<F, C> {
     fn order() -> BigUint {
         C::group_order()
     }
+
+    /// Montgomery ladder over complete formulas, whatever the curve model.
+    fn mul_secret(self, scalar: &BigInt) -> Self {
+        let order = Self::order();
+        let scalar = reduce_scalar(scalar, &order);
+        let (a, b) = (C::a(), C::b());
+        let a_is_minus_three = C::A_IS_MINUS_THREE;
+        let add = |lhs, rhs| {
+            if a_is_minus_three {
+                coordinates::complete_add_minus_three(lhs, rhs, b.clone())
+            } else {
+                coordinates::complete_add(lhs, rhs, a.clone(), b.clone())
+            }
+        };
+        let model = C::model();
+        let result = montgomery_ladder(
+            add,
+            &scalar,
+            order.bits(),
+            model.to_homogeneous(self.coords),
+            Model::Homogeneous.zero(),
+        );
+        Self::from_coords(model.from_homogeneous(result))
+    }
+}
+
+impl<F: Field, C> ConditionalSwap for EllipticPoint<F, C> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.coords.conditional_swap(&mut other.coords, mask);
+    }
 }
 
 impl<F: Clone, C> Clone for EllipticPoint<F, C> {
@@ -153,11 +368,10 @@ impl<F: Clone, C> Clone for EllipticPoint<F, C> {
 
 impl<F: Field, C: Curve<F>> PartialEq for EllipticPoint<F, C> {
     fn eq(&self, other: &Self) -> bool {
-        if self.is_zero() {
-            other.is_zero()
+        if self.is_zero() || other.is_zero() {
+            self.is_zero() && other.is_zero()
         } else {
-            self.x() * other.z() == other.x() * self.z()
-                && self.y() * other.z() == other.y() * self.z()
+            C::model().eq(&self.coords, &other.coords)
         }
     }
 }
@@ -168,38 +382,28 @@ impl<F: Field, C: Curve<F>> Add for EllipticPoint<F, C> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
+        let model = C::model();
+        if model == Model::Complete {
+            return Self::from_coords(Self::complete_add(
+                self.coords,
+                rhs.coords,
+            ));
+        }
         if self.is_zero() {
-            rhs
+            return rhs;
         } else if rhs.is_zero() {
-            self
-        } else if self == -rhs.clone() {
-            Self::zero()
-        } else if self == rhs {
-            let q = self.y() * self.z() * two();
-            let n = sq(self.x()) * three() + C::a() * sq(self.z());
-            let p = self.x() * sq(self.y()) * self.z() * four();
-            let u = sq(n.clone()) - p.clone() * two();
-
-            let x = u.clone() * q.clone();
-            let z = cube(q);
-            let eight = BigInt::from(8usize);
-            let y = n * (p - u) - sq(sq(self.y()) * self.z()) * eight;
-
-            Self::new(x, y, z)
-        } else {
-            let u = rhs.y() * self.z() - self.y() * rhs.z();
-            let v = rhs.x() * self.z() - self.x() * rhs.z();
-            let w = sq(u.clone()) * self.z() * rhs.z()
-                - cube(v.clone())
-                - sq(v.clone()) * two() * self.x() * rhs.z();
-            let q = cube(v.clone()) * self.y() * rhs.z();
-
-            let x = v.clone() * w.clone();
-            let z = self.z() * rhs.z() * cube(v.clone());
-            let y = u * (sq(v) * self.x() * rhs.z() - w) - q;
-
-            Self::new(x, y, z)
+            return self;
         }
+        let coords = match model {
+            Model::Jacobian => coordinates::jacobian_add(
+                self.coords,
+                rhs.coords,
+                C::a(),
+                C::A_IS_MINUS_THREE,
+            ),
+            _ => coordinates::homogeneous_add(self.coords, rhs.coords, C::a()),
+        };
+        Self::from_coords(coords)
     }
 }
 
@@ -220,14 +424,13 @@ impl<F: Field, C: Curve<F>> Neg for EllipticPoint<F, C> {
     }
 }
 
+/// Variable-time wNAF multiplication, doubling with the formulas of the
+/// curve model; use [`FinGroup::mul_secret`] for secret scalars.
 impl<F: Field, C: Curve<F>> Mul<BigInt> for EllipticPoint<F, C> {
     type Output = Self;
 
     fn mul(self, rhs: BigInt) -> Self::Output {
-        match rhs.try_into() {
-            Ok(rhs) => repeat_monoid(Self::add, rhs, self, Self::zero()),
-            Err(err) => -self * -err.into_original(),
-        }
+        scalar::mul_wnaf(self, &rhs, scalar::DEFAULT_WINDOW)
     }
 }
 
@@ -242,7 +445,7 @@ where
 
 impl<F: Field, C: Curve<F>> Zero for EllipticPoint<F, C> {
     fn zero() -> Self {
-        Self::new(F::zero(), F::one(), F::zero())
+        Self::from_coords(C::model().zero())
     }
 
     fn is_zero(&self) -> bool {
@@ -270,12 +473,91 @@ where
     }
 }
 
+impl<F, C> EllipticPoint<F, C>
+where
+    F: Field + FinGroup,
+    C: Curve<F>,
+    BigUint: From<F>,
+{
+    /// SEC1 encoding, see [`sec1`].
+    pub fn to_sec1(self, compressed: bool) -> Vec<u8> {
+        let coords = Option::<(F, F)>::from(self)
+            .map(|(x, y)| (BigUint::from(x), BigUint::from(y)));
+        sec1::encode(coords, sec1::field_len(&F::order()), compressed)
+    }
+
+    fn from_sec1(point: Sec1Point) -> Result<Self, Sec1Error>
+    where
+        F: Sqrt + From<BigUint>,
+    {
+        let field = |value: BigUint| {
+            if value < F::order() {
+                Ok(F::from(value))
+            } else {
+                Err(Sec1Error::NotInField)
+            }
+        };
+        match point {
+            Sec1Point::Infinity => Ok(Self::zero()),
+            Sec1Point::Compressed { x, odd } => {
+                let x = field(x)?;
+                let y = C::solve(x.clone()).ok_or(NotOnCurve)?;
+                if y.is_zero() && odd {
+                    Err(Sec1Error::WrongParity)
+                } else if BigUint::from(y.clone()).bit(0) == odd {
+                    Ok(Self::affine(x, y))
+                } else {
+                    Ok(Self::affine(x, -y))
+                }
+            }
+            Sec1Point::Uncompressed { x, y } => {
+                Ok(C::affine(field(x)?, field(y)?)?)
+            }
+        }
+    }
+}
+
+/// Uncompressed SEC1 encoding.
 impl<F, C> Serialize for EllipticPoint<F, C>
+where
+    F: Field + FinGroup,
+    C: Curve<F>,
+    BigUint: From<F>,
+{
+    fn serialize(self) -> Vec<u8> {
+        self.to_sec1(false)
+    }
+}
+
+/// Reads any SEC1 encoding, decompressing with [`Curve::solve`].
+impl<F, C> Deserialize for EllipticPoint<F, C>
+where
+    F: Field + FinGroup + Sqrt + From<BigUint>,
+    C: Curve<F>,
+    BigUint: From<F>,
+{
+    type Error = Sec1Error;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        sec1::read(stream, sec1::field_len(&F::order()))?
+            .map(Self::from_sec1)
+            .transpose()
+    }
+}
+
+/// Format used before SEC1: a `0` or `1` tag followed by the coordinates
+/// as serialized by the field.
+pub struct Legacy<T>(pub T);
+
+impl<F, C> Serialize for Legacy<EllipticPoint<F, C>>
 where
     F: Field + Serialize,
+    C: Curve<F>,
 {
     fn serialize(self) -> Vec<u8> {
-        match Option::<(F, F)>::from(self) {
+        match Option::<(F, F)>::from(self.0) {
             Some(points) => {
                 let mut result = vec![1];
                 result.append(&mut points.serialize());
@@ -286,7 +568,7 @@ where
     }
 }
 
-impl<F, C> Deserialize for EllipticPoint<F, C>
+impl<F, C> Deserialize for Legacy<EllipticPoint<F, C>>
 where
     F: Field + Deserialize,
     C: Curve<F>,
@@ -298,12 +580,12 @@ where
     ) -> Result<Option<Self>, Self::Error> {
         use PointDeserError::NotEnoughBytes;
         match stream.next() {
-            Some(0) => Ok(Some(EllipticPoint::zero())),
+            Some(0) => Ok(Some(Legacy(EllipticPoint::zero()))),
             Some(_) => {
                 let x = F::deserialize(stream)?.ok_or(NotEnoughBytes)?;
                 let y = F::deserialize(stream)?.ok_or(NotEnoughBytes)?;
                 C::affine(x, y)
-                    .map(Some)
+                    .map(|point| Some(Legacy(point)))
                     .map_err(PointDeserError::NotOnCurve)
             }
             None => Ok(None),
@@ -333,14 +615,6 @@ pub fn right_side<F: Field, C: Curve<F>>(x: F) -> F {
     cube(x.clone()) + C::a() * x + C::b()
 }
 
-fn two() -> BigInt {
-    BigInt::from(2usize)
-}
-
-fn three() -> BigInt {
-    BigInt::from(3usize)
-}
-
 fn four() -> BigInt {
     BigInt::from(4usize)
 }
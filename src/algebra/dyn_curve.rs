@@ -0,0 +1,546 @@
+use std::{
+    iter::Sum,
+    ops::{Add, Mul, Neg, Sub},
+    rc::Rc,
+    str::FromStr,
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Num, One, Zero};
+use thiserror::Error;
+
+use crate::bytes::Serialize;
+
+use super::{
+    algo::montgomery_ladder,
+    coordinates::{self, Coords, Model},
+    curve::NotOnCurve,
+    fields::{dynamic::DynZn, montgomery::MontgomeryContext},
+    scalar,
+    sec1::{self, Sec1Error, Sec1Point},
+    traits::{reduce_scalar, ConditionalSwap, DynFinGroup, Group, Sqrt},
+    validation::{validate_domain, Domain, ValidationReport},
+};
+
+/// Curve `y^2 = x^3 + ax + b` with a generator of prime order, all chosen at
+/// runtime. Points use Jacobian coordinates.
+#[derive(Debug)]
+pub struct DynCurve {
+    a: DynZn,
+    b: DynZn,
+    generator: (DynZn, DynZn),
+    order: BigUint,
+    cofactor: BigUint,
+    a_is_minus_three: bool,
+}
+
+impl DynCurve {
+    pub fn new(
+        modulus: BigUint,
+        (a, b): (BigUint, BigUint),
+        (x, y): (BigUint, BigUint),
+        order: BigUint,
+        cofactor: BigUint,
+    ) -> Result<Self, ValidationReport> {
+        let (p, n) = (modulus.clone(), order.clone());
+        let curve = validate_domain(&p, &n, || {
+            let context = Rc::new(MontgomeryContext::new(modulus));
+            let field = |value| DynZn::new(value, &context);
+            let (a, b) = (field(a), field(b));
+            let generator = (field(x), field(y));
+            let curve = Self {
+                a_is_minus_three: a == -a.residue(3),
+                a,
+                b,
+                generator: generator.clone(),
+                order,
+                cofactor,
+            };
+            (Rc::new(curve), generator)
+        })?;
+        Ok(Rc::try_unwrap(curve).unwrap())
+    }
+
+    pub fn field(&self) -> &Rc<MontgomeryContext> {
+        self.a.context()
+    }
+
+    pub fn order(&self) -> &BigUint {
+        &self.order
+    }
+
+    pub fn generator(self: &Rc<Self>) -> DynPoint {
+        let (x, y) = self.generator.clone();
+        DynPoint::affine(x, y, self.clone())
+    }
+
+    pub fn point(
+        self: &Rc<Self>,
+        x: DynZn,
+        y: DynZn,
+    ) -> Result<DynPoint, NotOnCurve> {
+        if self.contains(x.clone(), y.clone()) {
+            Ok(DynPoint::affine(x, y, self.clone()))
+        } else {
+            Err(NotOnCurve)
+        }
+    }
+
+    /// A byte `x` is the point `x * G`, where `G` is the generator.
+    pub fn encode_byte(self: &Rc<Self>, x: u8) -> DynPoint {
+        self.generator() * BigInt::from(x)
+    }
+
+    /// Finds the byte among the first 256 multiples of the generator.
+    pub fn decode_byte(
+        self: &Rc<Self>,
+        point: &DynPoint,
+    ) -> Result<u8, NotAByte> {
+        let generator = self.generator();
+        let mut multiple = DynPoint::zero();
+        for x in u8::MIN..=u8::MAX {
+            if *point == multiple {
+                return Ok(x);
+            }
+            multiple = multiple + generator.clone();
+        }
+        Err(NotAByte)
+    }
+
+    /// Reads any SEC1 encoding of a point on this curve, `None` if the
+    /// stream is empty.
+    pub fn read_point(
+        self: &Rc<Self>,
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<DynPoint>, Sec1Error> {
+        let len = sec1::field_len(self.field().modulus());
+        sec1::read(stream, len)?
+            .map(|point| self.from_sec1(point))
+            .transpose()
+    }
+
+    fn contains(&self, x: DynZn, y: DynZn) -> bool {
+        y.clone() * y == self.right_side(x)
+    }
+
+    fn right_side(&self, x: DynZn) -> DynZn {
+        x.clone() * x.clone() * x.clone() + self.a.clone() * x + self.b.clone()
+    }
+
+    fn from_sec1(
+        self: &Rc<Self>,
+        point: Sec1Point,
+    ) -> Result<DynPoint, Sec1Error> {
+        let field = |value: BigUint| {
+            if &value < self.field().modulus() {
+                Ok(DynZn::new(value, self.field()))
+            } else {
+                Err(Sec1Error::NotInField)
+            }
+        };
+        match point {
+            Sec1Point::Infinity => Ok(DynPoint::zero()),
+            Sec1Point::Compressed { x, odd } => {
+                let x = field(x)?;
+                let y = self.right_side(x.clone()).sqrt().ok_or(NotOnCurve)?;
+                if y.is_zero() && odd {
+                    Err(Sec1Error::WrongParity)
+                } else if BigUint::from(y.clone()).bit(0) == odd {
+                    Ok(DynPoint::affine(x, y, self.clone()))
+                } else {
+                    Ok(DynPoint::affine(x, -y, self.clone()))
+                }
+            }
+            Sec1Point::Uncompressed { x, y } => {
+                Ok(self.point(field(x)?, field(y)?)?)
+            }
+        }
+    }
+}
+
+impl Domain for Rc<DynCurve> {
+    type Field = DynZn;
+    type Point = DynPoint;
+
+    fn coefficients(&self) -> (DynZn, DynZn) {
+        (self.a.clone(), self.b.clone())
+    }
+
+    fn cofactor(&self) -> BigUint {
+        self.cofactor.clone()
+    }
+
+    fn constants(&self) -> (DynZn, DynZn) {
+        (self.a.residue(0), self.a.residue(1))
+    }
+
+    fn point(&self, x: DynZn, y: DynZn) -> Option<DynPoint> {
+        DynCurve::point(self, x, y).ok()
+    }
+}
+
+/// Parses lines `name = hex` with names `p`, `a`, `b`, `gx`, `gy`, `n` and
+/// the optional cofactor `h`, which defaults to 1. Blank lines and lines
+/// starting with `#` are ignored.
+impl FromStr for DynCurve {
+    type Err = CurveParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut params = vec![];
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| CurveParseError::BadLine(line.to_string()))?;
+            let value = BigUint::from_str_radix(value.trim(), 16)
+                .map_err(|_| CurveParseError::BadLine(line.to_string()))?;
+            params.push((name.trim().to_string(), value));
+        }
+        let get = |name: &'static str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .ok_or(CurveParseError::Missing(name))
+        };
+        Ok(DynCurve::new(
+            get("p")?,
+            (get("a")?, get("b")?),
+            (get("gx")?, get("gy")?),
+            get("n")?,
+            get("h").unwrap_or_else(|_| BigUint::one()),
+        )?)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CurveParseError {
+    #[error("expected `name = hex`, found `{0}`")]
+    BadLine(String),
+    #[error("parameter `{0}` is missing")]
+    Missing(&'static str),
+    #[error(transparent)]
+    Invalid(#[from] ValidationReport),
+}
+
+/// Point of a [`DynCurve`]. The point at infinity may have no curve attached,
+/// finite points always carry theirs.
+#[derive(Clone, Debug)]
+pub struct DynPoint(Option<(Coords<DynZn>, Rc<DynCurve>)>);
+
+impl DynPoint {
+    fn affine(x: DynZn, y: DynZn, curve: Rc<DynCurve>) -> Self {
+        let z = x.residue(1);
+        Self(Some(((x, y, z), curve)))
+    }
+
+    /// Point with Jacobian `coords` on the curve of `self`.
+    fn with_coords(&self, coords: Coords<DynZn>) -> Self {
+        match &self.0 {
+            _ if coords.2.is_zero() => Self::zero(),
+            Some((_, curve)) => Self(Some((coords, curve.clone()))),
+            None => unreachable!("finite point without a curve"),
+        }
+    }
+
+    fn finite(&self) -> Option<&(Coords<DynZn>, Rc<DynCurve>)> {
+        self.0.as_ref().filter(|((_, _, z), _)| !z.is_zero())
+    }
+
+    /// Point at infinity with coordinates on `curve`.
+    fn infinity(curve: &Rc<DynCurve>) -> Self {
+        let one = curve.a.residue(1);
+        let coords = (one.clone(), one, curve.a.residue(0));
+        Self(Some((coords, curve.clone())))
+    }
+
+    pub fn double(self) -> Self {
+        let coords = match self.finite() {
+            None => return self,
+            Some((coords, curve)) => coordinates::jacobian_double(
+                coords.clone(),
+                curve.a.clone(),
+                curve.a_is_minus_three,
+            ),
+        };
+        self.with_coords(coords)
+    }
+}
+
+impl From<DynPoint> for Option<(DynZn, DynZn)> {
+    fn from(point: DynPoint) -> Self {
+        point
+            .finite()
+            .map(|(coords, _)| Model::Jacobian.to_affine(coords.clone()))
+    }
+}
+
+impl Group for DynPoint {
+    fn double(self) -> Self {
+        DynPoint::double(self)
+    }
+}
+
+impl DynFinGroup for DynPoint {
+    /// Order of the generator, 1 for a point at infinity without a curve.
+    fn group_order(&self) -> BigUint {
+        self.0
+            .as_ref()
+            .map_or_else(BigUint::one, |(_, curve)| curve.order.clone())
+    }
+
+    /// Montgomery ladder over complete formulas, as for `EllipticPoint`.
+    fn mul_secret(self, scalar: &BigInt) -> Self {
+        let (coords, curve) = match self.finite() {
+            None => return self,
+            Some(point) => point,
+        };
+        let scalar = reduce_scalar(scalar, &curve.order);
+        let (a, b) = (curve.a.clone(), curve.b.clone());
+        let add = |lhs, rhs| {
+            if curve.a_is_minus_three {
+                coordinates::complete_add_minus_three(lhs, rhs, b.clone())
+            } else {
+                coordinates::complete_add(lhs, rhs, a.clone(), b.clone())
+            }
+        };
+        let infinity = (a.residue(0), a.residue(1), a.residue(0));
+        let result = montgomery_ladder(
+            add,
+            &scalar,
+            curve.order.bits(),
+            Model::Jacobian.to_homogeneous(coords.clone()),
+            infinity,
+        );
+        if result.2.is_zero() {
+            Self::zero()
+        } else {
+            self.with_coords(Model::Jacobian.from_homogeneous(result))
+        }
+    }
+}
+
+/// A point at infinity without a curve is first given coordinates on the
+/// curve of the other point.
+impl ConditionalSwap for DynPoint {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let curve = match (&self.0, &other.0) {
+            (Some((_, curve)), _) | (None, Some((_, curve))) => curve.clone(),
+            (None, None) => return,
+        };
+        for point in [&mut *self, &mut *other] {
+            if point.0.is_none() {
+                *point = Self::infinity(&curve);
+            }
+        }
+        if let (Some((lhs, _)), Some((rhs, other))) =
+            (&mut self.0, &mut other.0)
+        {
+            debug_assert!(Rc::ptr_eq(&curve, other));
+            lhs.0.conditional_swap(&mut rhs.0, mask);
+            lhs.1.conditional_swap(&mut rhs.1, mask);
+            lhs.2.conditional_swap(&mut rhs.2, mask);
+        }
+    }
+}
+
+impl PartialEq for DynPoint {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.finite(), other.finite()) {
+            (Some((lhs, _)), Some((rhs, _))) => Model::Jacobian.eq(lhs, rhs),
+            (lhs, rhs) => lhs.is_none() && rhs.is_none(),
+        }
+    }
+}
+
+impl Eq for DynPoint {}
+
+impl Add for DynPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let coords = match (self.finite(), rhs.finite()) {
+            (None, _) => return rhs,
+            (_, None) => return self,
+            _ if self == -rhs.clone() => return Self::zero(),
+            (Some((lhs, curve)), Some((rhs, _))) => coordinates::jacobian_add(
+                lhs.clone(),
+                rhs.clone(),
+                curve.a.clone(),
+                curve.a_is_minus_three,
+            ),
+        };
+        self.with_coords(coords)
+    }
+}
+
+impl Neg for DynPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self.0 {
+            Some(((x, y, z), curve)) => Self(Some(((x, -y, z), curve))),
+            None => self,
+        }
+    }
+}
+
+impl Sub for DynPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul<BigInt> for DynPoint {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        scalar::mul_wnaf(self, &rhs, 5)
+    }
+}
+
+impl Sum for DynPoint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl Zero for DynPoint {
+    fn zero() -> Self {
+        Self(None)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.finite().is_none()
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("point does not encode a byte")]
+pub struct NotAByte;
+
+/// Uncompressed SEC1 encoding.
+impl Serialize for DynPoint {
+    fn serialize(self) -> Vec<u8> {
+        let len = self
+            .0
+            .as_ref()
+            .map_or(0, |(_, curve)| sec1::field_len(curve.field().modulus()));
+        let coords = Option::<(DynZn, DynZn)>::from(self)
+            .map(|(x, y)| (x.into(), y.into()));
+        sec1::encode(coords, len, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use num_bigint::{BigInt, BigUint, RandBigInt};
+    use num_traits::{One, Zero};
+    use rand::thread_rng;
+
+    use super::{DynCurve, DynPoint, Sec1Error};
+    use crate::{
+        algebra::{
+            scalar::FixedBaseTable, traits::DynFinGroup,
+            validation::ValidationError::*,
+        },
+        bytes::Serialize,
+        ecges::{generator, hex},
+    };
+
+    const P224: &str = "
+        # NIST P-224
+        p = ffffffffffffffffffffffffffffffff000000000000000000000001
+        a = fffffffffffffffffffffffffffffffefffffffffffffffffffffffe
+        b = b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4
+        gx = b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21
+        gy = bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34
+        n = ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d
+    ";
+
+    fn p224() -> Rc<DynCurve> {
+        Rc::new(P224.parse().unwrap())
+    }
+
+    #[test]
+    fn matches_static_curve() {
+        let curve = p224();
+        assert!(curve.a_is_minus_three);
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let k = rng.gen_bigint(224);
+            let point = curve.generator() * k.clone();
+            assert_eq!(point.serialize(), (generator() * k).serialize());
+        }
+    }
+
+    #[test]
+    fn group_laws() {
+        let curve = p224();
+        let g = curve.generator();
+        assert!((g.clone() - g.clone()).is_zero());
+        assert!(g.clone() + DynPoint::zero() == g);
+        assert!(g.clone().double() == g.clone() * BigInt::from(2));
+        let k = BigInt::from(curve.order().clone()) - 5;
+        assert!(g.clone().mul_secret(&k) == g.clone() * BigInt::from(-5));
+        let table = FixedBaseTable::new(g.clone(), 4);
+        assert!(table.mul(&k) == g.clone() * BigInt::from(-5));
+        assert!(table.mul(&BigInt::zero()).is_zero());
+        let point = g * BigInt::from(12345);
+        let read = |bytes: Vec<u8>| {
+            let mut stream = bytes.into_iter();
+            let point = curve.read_point(&mut stream).unwrap().unwrap();
+            assert!(stream.next().is_none());
+            point
+        };
+        assert!(read(point.clone().serialize()) == point);
+        let compressed = (generator() * BigInt::from(12345)).to_sec1(true);
+        assert!(read(compressed) == point);
+        assert_eq!(curve.decode_byte(&curve.encode_byte(200)).unwrap(), 200);
+        assert!(curve.decode_byte(&point).is_err());
+    }
+
+    /// `y^2 = x^3 + 4x` of order `8 * 1277`, which has the point `(0, 0)`.
+    #[test]
+    fn compressed_zero_y() {
+        let curve: Rc<DynCurve> = Rc::new(
+            "p = 2735\n a = 4\n b = 0\n gx = 1bae\n gy = 1327\n n = 4fd\n h = 8"
+                .parse()
+                .unwrap(),
+        );
+        let read =
+            |bytes: [u8; 3]| curve.read_point(&mut bytes.iter().copied());
+        let point = read([2, 0, 0]).unwrap().unwrap();
+        assert!(!point.clone().is_zero() && point.clone().double().is_zero());
+        assert!(matches!(read([3, 0, 0]), Err(Sec1Error::WrongParity)));
+    }
+
+    #[test]
+    fn invalid() {
+        let parse = |s: String| s.parse::<DynCurve>().map(|_| ());
+        assert!(parse(P224.replace("n = ", "# n = ")).is_err());
+        let wrong = P224.replace("gy = bd", "gy = bc");
+        match parse(wrong).unwrap_err() {
+            super::CurveParseError::Invalid(report) => {
+                assert_eq!(report.errors, vec![GeneratorNotOnCurve])
+            }
+            err => panic!("{}", err),
+        }
+        let order =
+            hex("ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d");
+        let result = DynCurve::new(
+            hex("ffffffffffffffffffffffffffffffff000000000000000000000001"),
+            (BigUint::zero(), BigUint::zero()),
+            (BigUint::zero(), BigUint::zero()),
+            order,
+            BigUint::one(),
+        );
+        assert_eq!(result.unwrap_err().errors, vec![Singular]);
+    }
+}
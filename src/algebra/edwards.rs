@@ -0,0 +1,282 @@
+use std::{
+    convert::TryInto,
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+
+use super::{
+    algo::repeat_monoid,
+    curve::NotOnCurve,
+    traits::{ConditionalSwap, Field, FinGroup, Group, Sqrt},
+};
+
+/// Twisted Edwards curve `a * x^2 + y^2 = 1 + d * x^2 * y^2`.
+///
+/// Addition formulas are complete when `a` is a square and `d` is not.
+pub trait EdwardsCurve<F: Field>: Sized {
+    /// Order of the prime order subgroup.
+    fn group_order() -> BigUint;
+
+    /// Number of points on the curve divided by `group_order`.
+    fn cofactor() -> BigUint {
+        BigUint::one()
+    }
+
+    fn a() -> F;
+    fn d() -> F;
+
+    fn affine(x: F, y: F) -> Result<EdwardsPoint<F, Self>, NotOnCurve> {
+        let (xx, yy) = (x.clone() * x.clone(), y.clone() * y.clone());
+        let lhs = Self::a() * xx.clone() + yy.clone();
+        if lhs == F::one() + Self::d() * xx * yy {
+            Ok(EdwardsPoint::affine(x, y))
+        } else {
+            Err(NotOnCurve)
+        }
+    }
+
+    /// Some `x` such that `(x, y)` is on the curve.
+    fn solve(y: F) -> Option<F>
+    where
+        F: Sqrt,
+    {
+        let yy = y.clone() * y;
+        let denominator = Self::d() * yy.clone() - Self::a();
+        if denominator.is_zero() {
+            return None;
+        }
+        ((yy - F::one()) / denominator).sqrt()
+    }
+}
+
+/// Point in extended coordinates `(X : Y : Z : T)`, standing for
+/// `x = X / Z`, `y = Y / Z` with `x * y = T / Z`.
+#[derive(Debug)]
+pub struct EdwardsPoint<F, C> {
+    coords: (F, F, F, F),
+    curve: PhantomData<C>,
+}
+
+impl<F, C> EdwardsPoint<F, C> {
+    fn from_coords(coords: (F, F, F, F)) -> Self {
+        Self {
+            coords,
+            curve: PhantomData,
+        }
+    }
+}
+
+impl<F: Field, C> EdwardsPoint<F, C> {
+    fn affine(x: F, y: F) -> Self {
+        let t = x.clone() * y.clone();
+        Self::from_coords((x, y, F::one(), t))
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> From<EdwardsPoint<F, C>> for (F, F) {
+    fn from(point: EdwardsPoint<F, C>) -> Self {
+        let (x, y, z, _) = point.coords;
+        let inv = F::one() / z;
+        (x * inv.clone(), y * inv)
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Group for EdwardsPoint<F, C> {
+    fn double(self) -> Self {
+        let (x, y, z, _) = self.coords;
+        let a = x.clone() * x.clone();
+        let b = y.clone() * y.clone();
+        let c = z.clone() * z * BigInt::from(2);
+        let d = C::a() * a.clone();
+        let xy = x + y;
+        let e = xy.clone() * xy - a - b.clone();
+        let g = d.clone() + b.clone();
+        let f = g.clone() - c;
+        let h = d - b;
+        Self::from_coords((
+            e.clone() * f.clone(),
+            g.clone() * h.clone(),
+            f * g,
+            e * h,
+        ))
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> FinGroup for EdwardsPoint<F, C> {
+    fn order() -> BigUint {
+        C::group_order()
+    }
+}
+
+impl<F: Field, C> ConditionalSwap for EdwardsPoint<F, C> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.coords.conditional_swap(&mut other.coords, mask);
+    }
+}
+
+impl<F: Clone, C> Clone for EdwardsPoint<F, C> {
+    fn clone(&self) -> Self {
+        Self::from_coords(self.coords.clone())
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> PartialEq for EdwardsPoint<F, C> {
+    fn eq(&self, other: &Self) -> bool {
+        let (x1, y1, z1, _) = self.coords.clone();
+        let (x2, y2, z2, _) = other.coords.clone();
+        x1 * z2.clone() == x2 * z1.clone() && y1 * z2 == y2 * z1
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Eq for EdwardsPoint<F, C> {}
+
+impl<F: Field, C: EdwardsCurve<F>> Add for EdwardsPoint<F, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (x1, y1, z1, t1) = self.coords;
+        let (x2, y2, z2, t2) = rhs.coords;
+        let a = x1.clone() * x2.clone();
+        let b = y1.clone() * y2.clone();
+        let c = C::d() * t1 * t2;
+        let d = z1 * z2;
+        let e = (x1 + y1) * (x2 + y2) - a.clone() - b.clone();
+        let f = d.clone() - c.clone();
+        let g = d + c;
+        let h = b - C::a() * a;
+        Self::from_coords((
+            e.clone() * f.clone(),
+            g.clone() * h.clone(),
+            f * g,
+            e * h,
+        ))
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Sub for EdwardsPoint<F, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Neg for EdwardsPoint<F, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let (x, y, z, t) = self.coords;
+        Self::from_coords((-x, y, z, -t))
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Mul<BigInt> for EdwardsPoint<F, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        match rhs.try_into() {
+            Ok(rhs) => repeat_monoid(Self::add, rhs, self, Self::zero()),
+            Err(err) => -self * -err.into_original(),
+        }
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Sum for EdwardsPoint<F, C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl<F: Field, C: EdwardsCurve<F>> Zero for EdwardsPoint<F, C> {
+    fn zero() -> Self {
+        Self::affine(F::zero(), F::one())
+    }
+
+    fn is_zero(&self) -> bool {
+        let (x, y, z, _) = &self.coords;
+        x.is_zero() && y == z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint, RandBigInt};
+    use num_traits::Zero;
+    use rand::{thread_rng, Rng};
+
+    use super::{EdwardsCurve, EdwardsPoint};
+    use crate::algebra::{
+        fields::zn::{BigPrime, Zn},
+        traits::{FinGroup, Group},
+    };
+
+    type F = Zn<P10007>;
+    type Point = EdwardsPoint<F, Toy>;
+
+    fn random_point() -> Point {
+        let mut rng = thread_rng();
+        loop {
+            let y = F::from(rng.gen_range(0..10007usize));
+            if let Some(x) = Toy::solve(y.clone()) {
+                break Toy::affine(x, y).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn group_laws() {
+        for _ in 0..50 {
+            let (p, q, r) = (random_point(), random_point(), random_point());
+            assert!(p.clone() + q.clone() == q.clone() + p.clone());
+            assert!((p.clone() + q.clone()) + r.clone() == p.clone() + (q + r));
+            assert!(p.clone().double() == p.clone() + p.clone());
+            assert!((p.clone() - p.clone()).is_zero());
+            assert!((p * BigInt::from(10008)).is_zero());
+        }
+    }
+
+    #[test]
+    fn subgroup() {
+        let mut rng = thread_rng();
+        let cofactor = BigInt::from(Toy::cofactor());
+        for _ in 0..20 {
+            let point = random_point() * cofactor.clone();
+            assert!((point.clone() * BigInt::from(Point::order())).is_zero());
+            let k = rng.gen_bigint(20);
+            assert!(point.clone().mul_secret(&k) == point * k);
+        }
+    }
+
+    /// `x^2 + y^2 = 1 - x^2 * y^2`, with `8 * 9 * 139` points.
+    struct Toy;
+
+    impl EdwardsCurve<F> for Toy {
+        fn group_order() -> BigUint {
+            BigUint::from(139u32)
+        }
+
+        fn cofactor() -> BigUint {
+            BigUint::from(72u32)
+        }
+
+        fn a() -> F {
+            F::from(1)
+        }
+
+        fn d() -> F {
+            -F::from(1)
+        }
+    }
+
+    pub struct P10007;
+
+    impl BigPrime for P10007 {
+        fn value() -> BigUint {
+            BigUint::from(10007usize)
+        }
+    }
+}
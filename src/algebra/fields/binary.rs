@@ -0,0 +1,461 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Inv, One, Pow, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+use thiserror::Error;
+
+use crate::{
+    algebra::{
+        algo::repeat_monoid,
+        traits::{ConditionalSwap, Field, FinGroup, Group, Ring, Sqrt},
+    },
+    bytes::{Deserialize, Serialize},
+};
+
+/// Irreducible `x^DEGREE + sum of x^t for t in TERMS` over GF(2), which
+/// must fit into `L` 64-bit limbs, that is `DEGREE < 64 * L`.
+pub trait BinaryPoly<const L: usize> {
+    const DEGREE: usize;
+
+    /// Exponents of the lower terms, `0` included.
+    const TERMS: &'static [usize];
+}
+
+/// Element of GF(2^m) in polynomial basis, bit `i` of the limbs being the
+/// coefficient of `x^i`.
+pub struct Gf2m<P, const L: usize>([u64; L], PhantomData<P>);
+
+impl<P: BinaryPoly<L>, const L: usize> Gf2m<P, L> {
+    fn raw(limbs: [u64; L]) -> Self {
+        Self(limbs, PhantomData)
+    }
+
+    /// Sum of the conjugates `x^(2^i)`, either zero or one.
+    pub fn trace(&self) -> bool {
+        let mut power = self.clone();
+        let mut sum = self.clone();
+        for _ in 1..P::DEGREE {
+            power = power.clone() * power;
+            sum = sum + power.clone();
+        }
+        !sum.is_zero()
+    }
+
+    /// Solution `z` of `z^2 + z = self` for odd `m`, if the trace is zero.
+    pub fn half_trace(self) -> Option<Self> {
+        assert!(P::DEGREE % 2 == 1);
+        let mut result = self.clone();
+        for _ in 0..(P::DEGREE - 1) / 2 {
+            result = result.clone() * result.clone();
+            result = result.clone() * result + self.clone();
+        }
+        if result.clone() * result.clone() + result.clone() == self {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Reduces a polynomial of any degree modulo the irreducible one.
+    fn reduce(mut poly: Vec<u64>) -> Self {
+        for i in (P::DEGREE..poly.len() * 64).rev() {
+            if get_bit(&poly, i) {
+                flip_bit(&mut poly, i);
+                for &term in P::TERMS {
+                    flip_bit(&mut poly, i - P::DEGREE + term);
+                }
+            }
+        }
+        let mut limbs = [0; L];
+        for (limb, word) in limbs.iter_mut().zip(poly) {
+            *limb = word;
+        }
+        Self::raw(limbs)
+    }
+
+    fn modulus() -> [u64; L] {
+        let mut modulus = [0; L];
+        flip_bit(&mut modulus, P::DEGREE);
+        for &term in P::TERMS {
+            flip_bit(&mut modulus, term);
+        }
+        modulus
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Group for Gf2m<P, L> {}
+
+impl<P: BinaryPoly<L>, const L: usize> FinGroup for Gf2m<P, L> {
+    fn order() -> BigUint {
+        BigUint::one() << P::DEGREE
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Ring for Gf2m<P, L> {}
+
+impl<P: BinaryPoly<L>, const L: usize> Field for Gf2m<P, L> {}
+
+impl<P, const L: usize> ConditionalSwap for Gf2m<P, L> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> From<BigUint> for Gf2m<P, L> {
+    fn from(n: BigUint) -> Self {
+        Self::reduce(n.iter_u64_digits().collect())
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> From<usize> for Gf2m<P, L> {
+    fn from(x: usize) -> Self {
+        Self::reduce(vec![x as u64])
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> From<Gf2m<P, L>> for BigUint {
+    fn from(x: Gf2m<P, L>) -> Self {
+        let digits =
+            x.0.iter()
+                .flat_map(|limb| [*limb as u32, (*limb >> 32) as u32])
+                .collect();
+        BigUint::new(digits)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Distribution<Gf2m<P, L>> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Gf2m<P, L> {
+        let mut limbs = [0; L];
+        rng.fill(&mut limbs[..]);
+        Gf2m::reduce(limbs.to_vec())
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Debug for Gf2m<P, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Gf2m({:x})", BigUint::from(self.clone()))
+    }
+}
+
+impl<P, const L: usize> Clone for Gf2m<P, L> {
+    fn clone(&self) -> Self {
+        Self(self.0, PhantomData)
+    }
+}
+
+impl<P, const L: usize> PartialEq for Gf2m<P, L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P, const L: usize> Eq for Gf2m<P, L> {}
+
+impl<P: BinaryPoly<L>, const L: usize> Sqrt for Gf2m<P, L> {
+    /// Squaring is a bijection, the root is `x^(2^(m - 1))`.
+    fn sqrt(self) -> Option<Self> {
+        let mut result = self;
+        for _ in 1..P::DEGREE {
+            result = result.clone() * result;
+        }
+        Some(result)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Add for Gf2m<P, L> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (limb, other) in self.0.iter_mut().zip(&rhs.0) {
+            *limb ^= other;
+        }
+        self
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Neg for Gf2m<P, L> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Sub for Gf2m<P, L> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + rhs
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Mul<BigInt> for Gf2m<P, L> {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        if rhs.bit(0) {
+            self
+        } else {
+            Self::zero()
+        }
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Sum for Gf2m<P, L> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Mul for Gf2m<P, L> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut product = vec![0; 2 * L];
+        for i in (0..P::DEGREE).filter(|&i| get_bit(&rhs.0, i)) {
+            let (words, bits) = (i / 64, i % 64);
+            for (j, limb) in self.0.iter().enumerate() {
+                product[j + words] ^= limb << bits;
+                if bits > 0 {
+                    product[j + words + 1] ^= limb >> (64 - bits);
+                }
+            }
+        }
+        Self::reduce(product)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Pow<BigUint> for Gf2m<P, L> {
+    type Output = Self;
+
+    fn pow(self, rhs: BigUint) -> Self::Output {
+        repeat_monoid(Self::mul, rhs, self, Self::one())
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Product for Gf2m<P, L> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Self::mul)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Inv for Gf2m<P, L> {
+    type Output = Self;
+
+    /// Extended Euclidean algorithm over GF(2)[x].
+    fn inv(self) -> Self::Output {
+        assert!(!self.is_zero());
+        let (mut u, mut v) = (self.0, Self::modulus());
+        let (mut g1, mut g2) = (Self::one().0, [0; L]);
+        while degree(&u) > 0 {
+            let (du, dv) = (degree(&u), degree(&v));
+            if du < dv {
+                std::mem::swap(&mut u, &mut v);
+                std::mem::swap(&mut g1, &mut g2);
+            }
+            let shift = degree(&u) - degree(&v);
+            xor_shifted(&mut u, &v, shift);
+            xor_shifted(&mut g1, &g2, shift);
+        }
+        Self::raw(g1)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Div for Gf2m<P, L> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Zero for Gf2m<P, L> {
+    fn zero() -> Self {
+        Self::raw([0; L])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(Zero::is_zero)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> One for Gf2m<P, L> {
+    fn one() -> Self {
+        let mut limbs = [0; L];
+        limbs[0] = 1;
+        Self::raw(limbs)
+    }
+}
+
+impl<P: BinaryPoly<L>, const L: usize> Serialize for Gf2m<P, L> {
+    fn serialize(self) -> Vec<u8> {
+        let mut result: Vec<_> =
+            self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        result.truncate(P::DEGREE.div_ceil(8));
+        result
+    }
+}
+
+/// Reads exactly `ceil(m / 8)` bytes, which must not set bits beyond
+/// `x^(m - 1)`.
+impl<P: BinaryPoly<L>, const L: usize> Deserialize for Gf2m<P, L> {
+    type Error = Gf2mDeserError;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        let len = P::DEGREE.div_ceil(8);
+        let vec: Vec<_> = stream.take(len).collect();
+        if vec.is_empty() {
+            return Ok(None);
+        } else if vec.len() < len {
+            return Err(Gf2mDeserError::NotEnoughBytes);
+        }
+        let mut limbs = [0; L];
+        for (i, byte) in vec.into_iter().enumerate() {
+            limbs[i / 8] |= (byte as u64) << (8 * (i % 8));
+        }
+        if degree(&limbs) >= P::DEGREE {
+            Err(Gf2mDeserError::NotReduced)
+        } else {
+            Ok(Some(Self::raw(limbs)))
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Gf2mDeserError {
+    #[error("Not enough bytes")]
+    NotEnoughBytes,
+    #[error("Element has bits beyond the field degree")]
+    NotReduced,
+}
+
+fn get_bit(limbs: &[u64], i: usize) -> bool {
+    limbs[i / 64] >> (i % 64) & 1 == 1
+}
+
+fn flip_bit(limbs: &mut [u64], i: usize) {
+    limbs[i / 64] ^= 1 << (i % 64);
+}
+
+/// Degree of a nonzero polynomial, `0` for zero as well.
+fn degree(limbs: &[u64]) -> usize {
+    limbs
+        .iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| 64 * i + 63 - limbs[i].leading_zeros() as usize)
+}
+
+/// `lhs ^= rhs * x^shift`, dropping bits beyond the limbs.
+fn xor_shifted(lhs: &mut [u64], rhs: &[u64], shift: usize) {
+    let (words, bits) = (shift / 64, shift % 64);
+    for j in (0..lhs.len() - words).rev() {
+        let mut word = rhs[j] << bits;
+        if bits > 0 && j > 0 {
+            word |= rhs[j - 1] >> (64 - bits);
+        }
+        lhs[j + words] ^= word;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::{Inv, One, Pow, Zero};
+    use rand::{thread_rng, Rng};
+
+    use super::{BinaryPoly, Gf2m, Gf2mDeserError};
+    use crate::{
+        algebra::traits::{FinGroup, Sqrt},
+        bytes::{consume_deser, ConsumeDeserError, Serialize},
+        curves::koblitz::Gf163,
+    };
+
+    /// `x^8 + x^4 + x^3 + x + 1` from AES.
+    struct Aes;
+
+    impl BinaryPoly<1> for Aes {
+        const DEGREE: usize = 8;
+        const TERMS: &'static [usize] = &[4, 3, 1, 0];
+    }
+
+    type Gf256 = Gf2m<Aes, 1>;
+
+    /// Examples from FIPS 197.
+    #[test]
+    fn aes() {
+        assert!(Gf256::from(0x57) + Gf256::from(0x83) == Gf256::from(0xd4));
+        assert!(Gf256::from(0x57) * Gf256::from(0x83) == Gf256::from(0xc1));
+        assert!(Gf256::from(0x57) * Gf256::from(0x13) == Gf256::from(0xfe));
+        assert!(Gf256::from(0x53).inv() == Gf256::from(0xca));
+        assert!(Gf256::from(0x11b).is_zero());
+        for x in 1..256 {
+            let x = Gf256::from(x);
+            assert!(x.clone() * x.clone().inv() == Gf256::one());
+            assert!(x.clone().pow(Gf256::order() - 1u32) == Gf256::one());
+        }
+    }
+
+    #[test]
+    fn large() {
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let (x, y, z): (Gf163, Gf163, Gf163) =
+                (rng.gen(), rng.gen(), rng.gen());
+            assert!(
+                x.clone() * (y.clone() + z.clone())
+                    == x.clone() * y.clone() + x.clone() * z.clone()
+            );
+            assert!(
+                (x.clone() * y.clone()) * z.clone()
+                    == x.clone() * (y.clone() * z)
+            );
+            if !x.is_zero() {
+                assert!(x.clone() * x.clone().inv() == Gf163::one());
+                assert!(y.clone() / x.clone() * x.clone() == y);
+            }
+            let root = x.clone().sqrt().unwrap();
+            assert!(root.clone() * root == x);
+            if let Some(z) = x.clone().half_trace() {
+                assert!(!x.trace());
+                assert!(z.clone() * z.clone() + z == x);
+            } else {
+                assert!(x.trace());
+            }
+        }
+        let x: Gf163 = rng.gen();
+        assert!(x.clone().pow(Gf163::order()) == x);
+    }
+
+    #[test]
+    fn serialize() {
+        let x = Gf163::from(BigUint::one() << 162usize);
+        let bytes = x.clone().serialize();
+        assert_eq!(bytes.len(), 21);
+        assert!(consume_deser::<Gf163>(bytes.clone()).unwrap() == x);
+        assert!(matches!(
+            consume_deser::<Gf163>(bytes[..20].to_vec()),
+            Err(ConsumeDeserError::Deserialization(
+                Gf2mDeserError::NotEnoughBytes
+            ))
+        ));
+        let mut unreduced = bytes;
+        unreduced[20] |= 1 << 3;
+        assert!(matches!(
+            consume_deser::<Gf163>(unreduced),
+            Err(ConsumeDeserError::Deserialization(
+                Gf2mDeserError::NotReduced
+            ))
+        ));
+    }
+}
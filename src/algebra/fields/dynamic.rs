@@ -0,0 +1,310 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, Div, Mul, Neg, Sub},
+    rc::Rc,
+};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{Inv, One, Pow, Zero};
+
+use crate::{
+    algebra::{
+        algo::{extended_gcd, repeat_monoid, sqrt_mod},
+        fields::montgomery::MontgomeryContext,
+        traits::{ConditionalSwap, Field, Group, Ring, Sqrt},
+    },
+    bytes::Serialize,
+};
+
+/// Residue modulo a prime chosen at runtime.
+///
+/// Every value carries its modulus, so constants are created from an
+/// existing residue with [`DynZn::residue`] or from a context with
+/// [`DynZn::new`]. Combining residues of different moduli is a logic error.
+#[derive(Clone)]
+pub struct DynZn {
+    value: BigUint,
+    context: Rc<MontgomeryContext>,
+}
+
+impl DynZn {
+    pub fn new(value: BigUint, context: &Rc<MontgomeryContext>) -> Self {
+        let value = context.enter(value % context.modulus());
+        Self::mont(value, context.clone())
+    }
+
+    pub fn context(&self) -> &Rc<MontgomeryContext> {
+        &self.context
+    }
+
+    /// Residue of `value` with the same modulus as `self`.
+    pub fn residue(&self, value: usize) -> Self {
+        Self::new(value.into(), &self.context)
+    }
+
+    fn mont(value: BigUint, context: Rc<MontgomeryContext>) -> Self {
+        Self { value, context }
+    }
+
+    fn lift(value: BigInt, context: &MontgomeryContext) -> BigUint {
+        let reduced = value.magnitude() % context.modulus();
+        let reduced = match value.sign() {
+            Sign::Minus if !reduced.is_zero() => context.modulus() - reduced,
+            _ => reduced,
+        };
+        context.enter(reduced)
+    }
+
+    fn check(&self, rhs: &Self) {
+        debug_assert_eq!(self.context.modulus(), rhs.context.modulus());
+    }
+}
+
+impl Group for DynZn {}
+
+impl Ring for DynZn {}
+
+impl Field for DynZn {}
+
+impl ConditionalSwap for DynZn {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.check(other);
+        self.value.conditional_swap(&mut other.value, mask);
+    }
+}
+
+impl From<DynZn> for BigUint {
+    fn from(x: DynZn) -> Self {
+        x.context.leave(x.value)
+    }
+}
+
+impl Debug for DynZn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynZn")
+            .field(&self.context.leave(self.value.clone()))
+            .field(self.context.modulus())
+            .finish()
+    }
+}
+
+impl PartialEq for DynZn {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+            && self.context.modulus() == other.context.modulus()
+    }
+}
+
+impl Eq for DynZn {}
+
+impl Sqrt for DynZn {
+    fn sqrt(self) -> Option<Self> {
+        let (unit, context) = (self.residue(1), self.context.clone());
+        sqrt_mod(self, unit, context.modulus())
+    }
+}
+
+impl Add for DynZn {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check(&rhs);
+        let sum = self.value + rhs.value;
+        if &sum >= self.context.modulus() {
+            Self::mont(sum - self.context.modulus(), self.context)
+        } else {
+            Self::mont(sum, self.context)
+        }
+    }
+}
+
+impl Neg for DynZn {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.value.is_zero() {
+            self
+        } else {
+            Self::mont(self.context.modulus() - self.value, self.context)
+        }
+    }
+}
+
+impl Sub for DynZn {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul<BigInt> for DynZn {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        let rhs =
+            Self::mont(Self::lift(rhs, &self.context), self.context.clone());
+        self * rhs
+    }
+}
+
+/// Sums of residues need at least one term to know the modulus.
+impl Sum for DynZn {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Self::add).unwrap_or_else(Self::zero)
+    }
+}
+
+impl Mul for DynZn {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check(&rhs);
+        let value = self.context.reduce(self.value * rhs.value);
+        Self::mont(value, self.context)
+    }
+}
+
+impl Pow<BigUint> for DynZn {
+    type Output = Self;
+
+    fn pow(self, rhs: BigUint) -> Self::Output {
+        let one = self.residue(1);
+        repeat_monoid(Self::mul, rhs, self, one)
+    }
+}
+
+/// Products of residues need at least one factor to know the modulus.
+impl Product for DynZn {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Self::mul).unwrap_or_else(Self::one)
+    }
+}
+
+impl Inv for DynZn {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        let n = BigInt::from(self.context.modulus().clone());
+        let value = BigInt::from(self.context.leave(self.value));
+        let (gcd, inv, _) = extended_gcd(value, n);
+        assert!(gcd.is_one());
+        Self::mont(Self::lift(inv, &self.context), self.context)
+    }
+}
+
+impl Div for DynZn {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+/// Only present to satisfy [`Field`]: a residue without a value has no
+/// modulus. Use [`DynZn::new`] or [`DynZn::residue`] instead.
+impl Zero for DynZn {
+    fn zero() -> Self {
+        panic!("DynZn::zero has no modulus, use DynZn::residue(0)")
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+/// Only present to satisfy [`Field`]: a residue without a value has no
+/// modulus. Use [`DynZn::new`] or [`DynZn::residue`] instead.
+impl One for DynZn {
+    fn one() -> Self {
+        panic!("DynZn::one has no modulus, use DynZn::residue(1)")
+    }
+
+    fn is_one(&self) -> bool {
+        self.context.leave(self.value.clone()).is_one()
+    }
+}
+
+impl Serialize for DynZn {
+    fn serialize(self) -> Vec<u8> {
+        let bytes = self.context.bytes();
+        let mut result = BigUint::from(self).to_bytes_le();
+        result.resize(bytes, 0);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{Inv, One, Pow, Zero};
+    use rand::{thread_rng, Rng};
+
+    use super::DynZn;
+    use crate::{
+        algebra::{
+            fields::{
+                montgomery::MontgomeryContext,
+                zn::{BigPrime, Zn},
+            },
+            traits::Sqrt,
+        },
+        bytes::Serialize,
+    };
+
+    #[test]
+    fn constants() {
+        let context = Rc::new(MontgomeryContext::new(BigUint::from(17u32)));
+        let x = DynZn::new(BigUint::from(8u32), &context);
+        assert!(x.clone().inv() == x.residue(15));
+        assert!(x.clone() * x.residue(1) == x);
+        assert!((x.clone() - x.clone()).is_zero());
+        assert!(-x.residue(1) == x.residue(16));
+        assert!(x.residue(25) == x);
+        assert!(x.clone().pow(BigUint::zero()).is_one());
+        let other = Rc::new(MontgomeryContext::new(BigUint::from(19u32)));
+        assert!(DynZn::new(BigUint::from(8u32), &other) != x);
+    }
+
+    #[test]
+    fn matches_zn() {
+        let context = Rc::new(MontgomeryContext::new(Z10009::value()));
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let (a, b) = (rng.gen_biguint(64), rng.gen_biguint(64));
+            let (za, zb) = (Zn::<Z10009>::from(a.clone()), Zn::from(b.clone()));
+            let (da, db) = (DynZn::new(a, &context), DynZn::new(b, &context));
+            let same = |z: Zn<Z10009>, d: DynZn| BigUint::from(z) == d.into();
+            assert!(same(za.clone() + zb.clone(), da.clone() + db.clone()));
+            assert!(same(za.clone() - zb.clone(), da.clone() - db.clone()));
+            assert!(same(za.clone() * zb.clone(), da.clone() * db.clone()));
+            let e = BigUint::from(rng.gen::<u32>());
+            assert!(same(za.clone().pow(e.clone()), da.clone().pow(e)));
+            if !za.is_zero() {
+                assert!(same(za.inv(), da.clone().inv()));
+                let square = da.clone() * da;
+                let root = square.clone().sqrt().unwrap();
+                assert!(root.clone() * root == square);
+            }
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let context = Rc::new(MontgomeryContext::new(Z10009::value()));
+        let x = DynZn::new(BigUint::from(1234u32), &context);
+        assert_eq!(x.serialize(), Zn::<Z10009>::from(1234).serialize());
+    }
+
+    pub struct Z10009;
+
+    impl BigPrime for Z10009 {
+        fn value() -> BigUint {
+            BigUint::from(10009usize)
+        }
+    }
+}
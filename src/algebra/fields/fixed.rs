@@ -0,0 +1,597 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Inv, One, Pow, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use crate::{
+    algebra::{
+        algo::{repeat_monoid, sqrt_mod},
+        fields::zn::{BigPrime, Zn},
+        traits::{ConditionalSwap, Field, FinGroup, Group, Ring, Sqrt},
+    },
+    bytes::{Deserialize, Serialize},
+};
+
+/// Prime which fits into `L` 64-bit limbs, implemented by [`fixed_prime!`].
+///
+/// Only `MODULUS` has to be provided, Montgomery constants are derived from
+/// it at compile time. It must be equal to [`BigPrime::value`], which is
+/// checked by a debug assertion when [`FixedPrime::modulus`] is first built.
+pub trait FixedPrime<const L: usize>: BigPrime {
+    /// Little-endian limbs of the modulus.
+    const MODULUS: [u64; L];
+    /// `-MODULUS^-1 mod 2^64`.
+    const INV: u64 = neg_inv(Self::MODULUS[0]);
+    /// `R mod MODULUS` for `R = 2^(64 * L)`, i. e. Montgomery form of one.
+    const R: [u64; L] = pow2_mod(64 * L, &Self::MODULUS);
+    /// `R^2 mod MODULUS`, used to enter Montgomery form.
+    const R2: [u64; L] = pow2_mod(128 * L, &Self::MODULUS);
+
+    /// `MODULUS` as a `BigUint`, built once and kept in a static.
+    fn modulus() -> &'static BigUint;
+}
+
+/// Implements [`FixedPrime`] for a [`BigPrime`] type with the given limbs.
+macro_rules! fixed_prime {
+    ($prime:ty, $limbs:literal, $modulus:expr) => {
+        impl $crate::algebra::fields::fixed::FixedPrime<$limbs> for $prime {
+            const MODULUS: [u64; $limbs] = $modulus;
+
+            fn modulus() -> &'static num_bigint::BigUint {
+                static MODULUS: std::sync::OnceLock<num_bigint::BigUint> =
+                    std::sync::OnceLock::new();
+                MODULUS.get_or_init(
+                    $crate::algebra::fields::fixed::checked_modulus::<
+                        $prime,
+                        $limbs,
+                    >,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use fixed_prime;
+
+/// Builds `N::MODULUS` as a `BigUint` and compares it with `N::value()`.
+pub fn checked_modulus<N: FixedPrime<L>, const L: usize>() -> BigUint {
+    let modulus = to_biguint(&N::MODULUS);
+    debug_assert!(modulus == N::value(), "MODULUS differs from value()");
+    modulus
+}
+
+/// Parses a big-endian hex string (whitespace allowed) into limbs.
+pub const fn limbs<const L: usize>(hex: &str) -> [u64; L] {
+    let hex = hex.as_bytes();
+    let mut result = [0; L];
+    let mut digit = 0;
+    let mut i = hex.len();
+    while i > 0 {
+        i -= 1;
+        let value = match hex[i] {
+            b'0'..=b'9' => hex[i] - b'0',
+            b'a'..=b'f' => hex[i] - b'a' + 10,
+            b'A'..=b'F' => hex[i] - b'A' + 10,
+            b' ' | b'\n' | b'\t' => continue,
+            _ => panic!("not a hex digit"),
+        };
+        assert!(digit < 16 * L, "number does not fit into limbs");
+        result[digit / 16] |= (value as u64) << (4 * (digit % 16));
+        digit += 1;
+    }
+    result
+}
+
+const fn neg_inv(n0: u64) -> u64 {
+    // Newton iteration doubles the number of correct low bits each step.
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(n0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+const fn pow2_mod<const L: usize>(exp: usize, modulus: &[u64; L]) -> [u64; L] {
+    let mut result = [0; L];
+    result[0] = 1;
+    let mut i = 0;
+    while i < exp {
+        let (doubled, carry) = shl1(&result);
+        result = if carry || !less(&doubled, modulus) {
+            sub_limbs(&doubled, modulus).0
+        } else {
+            doubled
+        };
+        i += 1;
+    }
+    result
+}
+
+const fn shl1<const L: usize>(x: &[u64; L]) -> ([u64; L], bool) {
+    let mut result = [0; L];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < L {
+        result[i] = (x[i] << 1) | carry;
+        carry = x[i] >> 63;
+        i += 1;
+    }
+    (result, carry == 1)
+}
+
+const fn less<const L: usize>(x: &[u64; L], y: &[u64; L]) -> bool {
+    let mut i = L;
+    while i > 0 {
+        i -= 1;
+        if x[i] != y[i] {
+            return x[i] < y[i];
+        }
+    }
+    false
+}
+
+const fn add_limbs<const L: usize>(
+    x: &[u64; L],
+    y: &[u64; L],
+) -> ([u64; L], bool) {
+    let mut result = [0; L];
+    let mut carry = false;
+    let mut i = 0;
+    while i < L {
+        let (sum, c1) = x[i].overflowing_add(y[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        result[i] = sum;
+        carry = c1 || c2;
+        i += 1;
+    }
+    (result, carry)
+}
+
+const fn sub_limbs<const L: usize>(
+    x: &[u64; L],
+    y: &[u64; L],
+) -> ([u64; L], bool) {
+    let mut result = [0; L];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < L {
+        let (diff, b1) = x[i].overflowing_sub(y[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        result[i] = diff;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+/// Residue modulo `N` kept on the stack as `L` limbs in Montgomery form.
+pub struct FixedZn<N, const L: usize>([u64; L], PhantomData<N>);
+
+impl<N: FixedPrime<L>, const L: usize> FixedZn<N, L> {
+    fn raw(limbs: [u64; L]) -> Self {
+        Self(limbs, PhantomData)
+    }
+
+    /// Montgomery multiplication (CIOS): `x * y * R^-1 mod N`.
+    fn mont_mul(x: &[u64; L], y: &[u64; L]) -> [u64; L] {
+        let n = &N::MODULUS;
+        let mut t = [0u64; L];
+        let mut t_hi = 0u64;
+        for &y_i in y {
+            let mut carry = 0u128;
+            for j in 0..L {
+                let acc = t[j] as u128 + x[j] as u128 * y_i as u128 + carry;
+                t[j] = acc as u64;
+                carry = acc >> 64;
+            }
+            let acc = t_hi as u128 + carry;
+            t_hi = acc as u64;
+            let t_top = (acc >> 64) as u64;
+
+            let m = t[0].wrapping_mul(N::INV);
+            let mut carry = (t[0] as u128 + m as u128 * n[0] as u128) >> 64;
+            for j in 1..L {
+                let acc = t[j] as u128 + m as u128 * n[j] as u128 + carry;
+                t[j - 1] = acc as u64;
+                carry = acc >> 64;
+            }
+            let acc = t_hi as u128 + carry;
+            t[L - 1] = acc as u64;
+            t_hi = t_top + (acc >> 64) as u64;
+        }
+        if t_hi != 0 || !less(&t, n) {
+            t = sub_limbs(&t, n).0;
+        }
+        t
+    }
+
+    fn canonical(&self) -> [u64; L] {
+        let mut one = [0; L];
+        one[0] = 1;
+        Self::mont_mul(&self.0, &one)
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Group for FixedZn<N, L> {}
+
+impl<N: FixedPrime<L>, const L: usize> FinGroup for FixedZn<N, L> {
+    fn order() -> BigUint {
+        N::modulus().clone()
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Ring for FixedZn<N, L> {}
+
+impl<N: FixedPrime<L>, const L: usize> Field for FixedZn<N, L> {}
+
+impl<N, const L: usize> ConditionalSwap for FixedZn<N, L> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<BigUint> for FixedZn<N, L> {
+    fn from(n: BigUint) -> Self {
+        let n = n % N::modulus();
+        let mut limbs = [0; L];
+        for (limb, digit) in limbs.iter_mut().zip(n.iter_u64_digits()) {
+            *limb = digit;
+        }
+        Self::raw(Self::mont_mul(&limbs, &N::R2))
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<BigInt> for FixedZn<N, L> {
+    fn from(n: BigInt) -> Self {
+        Zn::<N>::from(n).into()
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<usize> for FixedZn<N, L> {
+    fn from(x: usize) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<Zn<N>> for FixedZn<N, L> {
+    fn from(x: Zn<N>) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<FixedZn<N, L>> for BigUint {
+    fn from(x: FixedZn<N, L>) -> Self {
+        to_biguint(&x.canonical())
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> From<FixedZn<N, L>> for Zn<N> {
+    fn from(x: FixedZn<N, L>) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Distribution<FixedZn<N, L>>
+    for Standard
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> FixedZn<N, L> {
+        FixedZn::from(rng.gen::<usize>())
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Debug for FixedZn<N, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FixedZn")
+            .field(&BigUint::from(self.clone()))
+            .finish()
+    }
+}
+
+impl<N, const L: usize> Clone for FixedZn<N, L> {
+    fn clone(&self) -> Self {
+        Self(self.0, PhantomData)
+    }
+}
+
+impl<N, const L: usize> PartialEq for FixedZn<N, L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<N, const L: usize> Eq for FixedZn<N, L> {}
+
+impl<N: FixedPrime<L>, const L: usize> Sqrt for FixedZn<N, L> {
+    fn sqrt(self) -> Option<Self> {
+        sqrt_mod(self, Self::one(), N::modulus())
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Add for FixedZn<N, L> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (sum, carry) = add_limbs(&self.0, &rhs.0);
+        if carry || !less(&sum, &N::MODULUS) {
+            Self::raw(sub_limbs(&sum, &N::MODULUS).0)
+        } else {
+            Self::raw(sum)
+        }
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Neg for FixedZn<N, L> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.is_zero() {
+            self
+        } else {
+            Self::raw(sub_limbs(&N::MODULUS, &self.0).0)
+        }
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Sub for FixedZn<N, L> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (diff, borrow) = sub_limbs(&self.0, &rhs.0);
+        if borrow {
+            Self::raw(add_limbs(&diff, &N::MODULUS).0)
+        } else {
+            Self::raw(diff)
+        }
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Mul<BigInt> for FixedZn<N, L> {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        self * Self::from(rhs)
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Sum for FixedZn<N, L> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Mul for FixedZn<N, L> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::raw(Self::mont_mul(&self.0, &rhs.0))
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Pow<BigUint> for FixedZn<N, L> {
+    type Output = Self;
+
+    fn pow(self, rhs: BigUint) -> Self::Output {
+        repeat_monoid(Self::mul, rhs, self, Self::one())
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Product for FixedZn<N, L> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Self::mul)
+    }
+}
+
+/// `self^(N - 2)` by square-and-multiply. The exponent is public, so the
+/// same sequence of multiplications is done for every element.
+impl<N: FixedPrime<L>, const L: usize> Inv for FixedZn<N, L> {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        assert!(!self.is_zero());
+        let exp = N::modulus() - 2u32;
+        (0..exp.bits()).rev().fold(Self::one(), |acc, i| {
+            let acc = acc.clone() * acc;
+            if exp.bit(i) {
+                acc * self.clone()
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Div for FixedZn<N, L> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Zero for FixedZn<N, L> {
+    fn zero() -> Self {
+        Self::raw([0; L])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&limb| limb == 0)
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> One for FixedZn<N, L> {
+    fn one() -> Self {
+        Self::raw(N::R)
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Serialize for FixedZn<N, L> {
+    fn serialize(self) -> Vec<u8> {
+        let bytes = (N::modulus().bits() as usize).div_ceil(8);
+        let mut result: Vec<_> = self
+            .canonical()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect();
+        result.truncate(bytes);
+        result
+    }
+}
+
+impl<N: FixedPrime<L>, const L: usize> Deserialize for FixedZn<N, L> {
+    type Error = <Zn<N> as Deserialize>::Error;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        Zn::<N>::deserialize(stream).map(|x| x.map(Self::from))
+    }
+}
+
+fn to_biguint(limbs: &[u64]) -> BigUint {
+    let digits = limbs
+        .iter()
+        .flat_map(|limb| [*limb as u32, (*limb >> 32) as u32])
+        .collect();
+    BigUint::new(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{Inv, One, Pow, Zero};
+    use rand::{thread_rng, Rng};
+
+    use crate::{
+        algebra::{
+            fields::{
+                fixed::{limbs, FixedZn},
+                zn::{BigPrime, Zn},
+            },
+            traits::FinGroup,
+        },
+        bytes::{consume_deser, Serialize},
+    };
+
+    #[test]
+    fn small() {
+        type F = FixedZn<Z47, 1>;
+        assert!((F::from(40) + F::from(7)).is_zero());
+        assert!(-F::from(111) == F::from(30));
+        assert!(F::from(8).inv() * F::from(8) == F::one());
+        assert!(F::from(3) - F::from(5) == F::from(45));
+        assert_eq!(BigUint::from(F::from(46) * F::from(46)), BigUint::one());
+    }
+
+    #[test]
+    fn matches_zn() {
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let (a, b) = (rng.gen_biguint(300), rng.gen_biguint(300));
+            let (za, zb) = (Zn::<P255>::from(a.clone()), Zn::from(b.clone()));
+            let (fa, fb) = (F255::from(a), F255::from(b));
+            assert!(
+                F255::from(za.clone() + zb.clone()) == fa.clone() + fb.clone()
+            );
+            assert!(
+                F255::from(za.clone() - zb.clone()) == fa.clone() - fb.clone()
+            );
+            assert!(
+                F255::from(za.clone() * zb.clone()) == fa.clone() * fb.clone()
+            );
+            let e = BigUint::from(rng.gen::<u32>());
+            assert!(F255::from(za.clone().pow(e.clone())) == fa.clone().pow(e));
+            if !za.is_zero() {
+                assert!(F255::from(za.inv()) == fa.inv());
+            }
+        }
+    }
+
+    #[test]
+    fn full_top_limb() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let (a, b) = (rng.gen_biguint(128), rng.gen_biguint(128));
+            let expected = &a * &b % P128::value();
+            let product = FixedZn::<P128, 2>::from(a) * FixedZn::from(b);
+            assert_eq!(BigUint::from(product), expected);
+        }
+    }
+
+    #[test]
+    fn modulus() {
+        assert_eq!(FixedZn::<Z47, 1>::order(), Z47::value());
+        assert_eq!(F255::order(), P255::value());
+        assert_eq!(FixedZn::<P128, 2>::order(), P128::value());
+    }
+
+    #[test]
+    #[should_panic(expected = "MODULUS differs from value()")]
+    fn wrong_modulus() {
+        FixedZn::<Wrong, 1>::order();
+    }
+
+    #[test]
+    fn serialize() {
+        let x = F255::from(123456789usize);
+        let bytes = x.clone().serialize();
+        assert_eq!(bytes, Zn::<P255>::from(123456789usize).serialize());
+        assert!(consume_deser::<F255>(bytes).unwrap() == x);
+    }
+
+    type F255 = FixedZn<P255, 4>;
+
+    pub struct Z47;
+    pub struct P255;
+    pub struct P128;
+
+    impl BigPrime for Z47 {
+        fn value() -> BigUint {
+            BigUint::from(47usize)
+        }
+    }
+
+    fixed_prime!(Z47, 1, [47]);
+
+    impl BigPrime for P255 {
+        fn value() -> BigUint {
+            (BigUint::one() << 255) - BigUint::from(19usize)
+        }
+    }
+
+    fixed_prime!(
+        P255,
+        4,
+        limbs(
+            "7fffffffffffffff ffffffffffffffff
+             ffffffffffffffff ffffffffffffffed"
+        )
+    );
+
+    /// Largest 128-bit prime, to exercise a saturated top limb.
+    impl BigPrime for P128 {
+        fn value() -> BigUint {
+            (BigUint::one() << 128) - BigUint::from(159usize)
+        }
+    }
+
+    fixed_prime!(P128, 2, limbs("ffffffffffffffffffffffffffffff61"));
+
+    pub struct Wrong;
+
+    impl BigPrime for Wrong {
+        fn value() -> BigUint {
+            BigUint::from(47usize)
+        }
+    }
+
+    fixed_prime!(Wrong, 1, [43]);
+}
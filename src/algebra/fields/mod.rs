@@ -1,2 +1,6 @@
+pub mod binary;
+pub mod dynamic;
+pub mod fixed;
+pub mod montgomery;
 pub mod poly;
 pub mod zn;
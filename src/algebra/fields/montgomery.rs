@@ -0,0 +1,442 @@
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug, Formatter},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Inv, One, Pow, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use crate::{
+    algebra::{
+        algo::{extended_gcd, repeat_monoid, sqrt_mod},
+        fields::zn::{BigPrime, Zn},
+        traits::{ConditionalSwap, Field, FinGroup, Group, Ring, Sqrt},
+    },
+    bytes::{Deserialize, Serialize},
+};
+
+/// Everything Montgomery arithmetic needs to know about a modulus `N`.
+///
+/// `R` is the smallest power of `2^64` greater than `N`.
+#[derive(Debug)]
+pub struct MontgomeryContext {
+    modulus: BigUint,
+    bytes: usize,
+    shift: usize,
+    mask: BigUint,
+    r: BigUint,
+    r2: BigUint,
+    neg_inv: BigUint,
+}
+
+impl MontgomeryContext {
+    pub fn new(modulus: BigUint) -> Self {
+        assert!(modulus.bit(0), "Montgomery form needs an odd modulus");
+        let bytes = modulus.to_bytes_le().len();
+        let shift = modulus.iter_u64_digits().len() * 64;
+        let big_r = BigUint::one() << shift;
+        let mask = &big_r - BigUint::one();
+        let r = &big_r % &modulus;
+        let r2 = &r * &r % &modulus;
+        let (gcd, inv, _) = extended_gcd(
+            BigInt::from(modulus.clone()),
+            BigInt::from(big_r.clone()),
+        );
+        assert!(gcd.is_one());
+        let inv = BigUint::try_from(inv % BigInt::from(big_r.clone()))
+            .unwrap_or_else(|err| &big_r - err.into_original().magnitude());
+        let neg_inv = (&big_r - inv) & &mask;
+        Self {
+            modulus,
+            bytes,
+            shift,
+            mask,
+            r,
+            r2,
+            neg_inv,
+        }
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Length of a serialized residue.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Montgomery reduction: `t * R^-1 mod N` for `t < N * R`.
+    pub(super) fn reduce(&self, t: BigUint) -> BigUint {
+        let m = ((&t & &self.mask) * &self.neg_inv) & &self.mask;
+        let u = (t + m * &self.modulus) >> self.shift;
+        if u >= self.modulus {
+            u - &self.modulus
+        } else {
+            u
+        }
+    }
+
+    pub(super) fn enter(&self, x: BigUint) -> BigUint {
+        self.reduce(x % &self.modulus * &self.r2)
+    }
+
+    pub(super) fn leave(&self, x: BigUint) -> BigUint {
+        self.reduce(x)
+    }
+}
+
+/// Modulus with a Montgomery context computed on first use and then kept in
+/// a static, implemented by [`montgomery_prime!`].
+pub trait MontgomeryPrime: BigPrime {
+    fn context() -> &'static MontgomeryContext;
+}
+
+/// Implements [`MontgomeryPrime`] for each of the given [`BigPrime`] types.
+macro_rules! montgomery_prime {
+    ($($prime:ty),* $(,)?) => {$(
+        impl $crate::algebra::fields::montgomery::MontgomeryPrime for $prime {
+            fn context(
+            ) -> &'static $crate::algebra::fields::montgomery::MontgomeryContext
+            {
+                use $crate::algebra::fields::{
+                    montgomery::MontgomeryContext, zn::BigPrime,
+                };
+                static CONTEXT: std::sync::OnceLock<MontgomeryContext> =
+                    std::sync::OnceLock::new();
+                CONTEXT.get_or_init(|| {
+                    MontgomeryContext::new(<$prime as BigPrime>::value())
+                })
+            }
+        }
+    )*};
+}
+
+pub(crate) use montgomery_prime;
+
+/// Residue modulo `N` stored in Montgomery form `x * R mod N`.
+///
+/// Multiplication needs no division, but every operation still allocates, so
+/// this is only about twice as fast as `Zn`. Prefer `FixedZn` when the size of
+/// the modulus is known at compile time; `--bench` compares all three.
+pub struct MontZn<N>(BigUint, PhantomData<N>);
+
+impl<N> MontZn<N> {
+    fn raw(value: BigUint) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<N: MontgomeryPrime> Group for MontZn<N> {}
+
+impl<N: MontgomeryPrime> FinGroup for MontZn<N> {
+    fn order() -> BigUint {
+        N::context().modulus.clone()
+    }
+}
+
+impl<N: MontgomeryPrime> Ring for MontZn<N> {}
+
+impl<N: MontgomeryPrime> Field for MontZn<N> {}
+
+impl<N> ConditionalSwap for MontZn<N> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
+}
+
+impl<N: MontgomeryPrime> From<BigUint> for MontZn<N> {
+    fn from(n: BigUint) -> Self {
+        Self::raw(N::context().enter(n))
+    }
+}
+
+impl<N: MontgomeryPrime> From<BigInt> for MontZn<N> {
+    fn from(n: BigInt) -> Self {
+        let modulus = N::context().modulus.clone();
+        match BigUint::try_from(n) {
+            Ok(n) => Self::from(n),
+            Err(err) => -Self::from(err.into_original().magnitude() % modulus),
+        }
+    }
+}
+
+impl<N: MontgomeryPrime> From<usize> for MontZn<N> {
+    fn from(x: usize) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: MontgomeryPrime> From<Zn<N>> for MontZn<N> {
+    fn from(x: Zn<N>) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: MontgomeryPrime> From<MontZn<N>> for BigUint {
+    fn from(x: MontZn<N>) -> Self {
+        N::context().leave(x.0)
+    }
+}
+
+impl<N: MontgomeryPrime> From<MontZn<N>> for Zn<N> {
+    fn from(x: MontZn<N>) -> Self {
+        Self::from(BigUint::from(x))
+    }
+}
+
+impl<N: MontgomeryPrime> Distribution<MontZn<N>> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> MontZn<N> {
+        MontZn::from(rng.gen::<usize>())
+    }
+}
+
+impl<N: MontgomeryPrime> Debug for MontZn<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MontZn")
+            .field(&BigUint::from(self.clone()))
+            .finish()
+    }
+}
+
+impl<N> Clone for MontZn<N> {
+    fn clone(&self) -> Self {
+        Self::raw(self.0.clone())
+    }
+}
+
+impl<N> PartialEq for MontZn<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<N> Eq for MontZn<N> {}
+
+impl<N: MontgomeryPrime> Sqrt for MontZn<N> {
+    fn sqrt(self) -> Option<Self> {
+        sqrt_mod(self, Self::one(), &Self::order())
+    }
+}
+
+impl<N: MontgomeryPrime> Add for MontZn<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let ctx = N::context();
+        let sum = self.0 + rhs.0;
+        if sum >= ctx.modulus {
+            Self::raw(sum - &ctx.modulus)
+        } else {
+            Self::raw(sum)
+        }
+    }
+}
+
+impl<N: MontgomeryPrime> Neg for MontZn<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0.is_zero() {
+            self
+        } else {
+            Self::raw(&N::context().modulus - self.0)
+        }
+    }
+}
+
+impl<N: MontgomeryPrime> Sub for MontZn<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.0 >= rhs.0 {
+            Self::raw(self.0 - rhs.0)
+        } else {
+            Self::raw(self.0 + &N::context().modulus - rhs.0)
+        }
+    }
+}
+
+impl<N: MontgomeryPrime> Mul<BigInt> for MontZn<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: BigInt) -> Self::Output {
+        self * Self::from(rhs)
+    }
+}
+
+impl<N: MontgomeryPrime> Sum for MontZn<N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Self::add)
+    }
+}
+
+impl<N: MontgomeryPrime> Mul for MontZn<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::raw(N::context().reduce(self.0 * rhs.0))
+    }
+}
+
+impl<N: MontgomeryPrime> Pow<BigUint> for MontZn<N> {
+    type Output = Self;
+
+    fn pow(self, rhs: BigUint) -> Self::Output {
+        repeat_monoid(Self::mul, rhs, self, Self::one())
+    }
+}
+
+impl<N: MontgomeryPrime> Product for MontZn<N> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Self::mul)
+    }
+}
+
+impl<N: MontgomeryPrime> Inv for MontZn<N> {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        let n: BigInt = Self::order().into();
+        let (gcd, inv, _) = extended_gcd(BigUint::from(self).into(), n);
+        assert!(gcd.is_one());
+        Self::from(inv)
+    }
+}
+
+impl<N: MontgomeryPrime> Div for MontZn<N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<N: MontgomeryPrime> Zero for MontZn<N> {
+    fn zero() -> Self {
+        Self::raw(BigUint::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<N: MontgomeryPrime> One for MontZn<N> {
+    fn one() -> Self {
+        Self::raw(N::context().r.clone())
+    }
+}
+
+impl<N: MontgomeryPrime> Serialize for MontZn<N> {
+    fn serialize(self) -> Vec<u8> {
+        let bytes = N::context().bytes;
+        let mut result = BigUint::from(self).to_bytes_le();
+        result.resize(bytes, 0);
+        result
+    }
+}
+
+impl<N: MontgomeryPrime> Deserialize for MontZn<N> {
+    type Error = <Zn<N> as Deserialize>::Error;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        Zn::<N>::deserialize(stream).map(|x| x.map(Self::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_bigint::RandBigInt;
+    use num_traits::{Inv, One, Pow, Zero};
+    use rand::{thread_rng, Rng};
+
+    use crate::{
+        algebra::fields::{
+            montgomery::MontZn,
+            zn::{BigPrime, Zn},
+        },
+        bytes::{consume_deser, Serialize},
+    };
+
+    #[test]
+    fn small() {
+        assert!((MontZn::<Z47>::from(40) + MontZn::from(7)).is_zero());
+        assert!(-MontZn::<Z47>::from(111) == MontZn::from(30));
+        assert!(MontZn::<Z17>::from(8).inv() == MontZn::from(15));
+        assert!(MontZn::<Z17>::from(3) - MontZn::from(5) == MontZn::from(15));
+        assert_eq!(BigUint::from(MontZn::<Z17>::one()), BigUint::one());
+    }
+
+    #[test]
+    fn matches_zn() {
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let (a, b): (BigUint, BigUint) = (
+                rng.gen_biguint(200) % Z127::value(),
+                rng.gen_biguint(200) % Z127::value(),
+            );
+            let (za, zb) = (Zn::<Z127>::from(a.clone()), Zn::from(b.clone()));
+            let (ma, mb) = (MontZn::<Z127>::from(a), MontZn::from(b));
+            assert!(
+                MontZn::from(za.clone() + zb.clone())
+                    == ma.clone() + mb.clone()
+            );
+            assert!(
+                MontZn::from(za.clone() - zb.clone())
+                    == ma.clone() - mb.clone()
+            );
+            assert!(
+                MontZn::from(za.clone() * zb.clone())
+                    == ma.clone() * mb.clone()
+            );
+            let e = BigUint::from(rng.gen::<u32>());
+            assert!(
+                MontZn::from(za.clone().pow(e.clone())) == ma.clone().pow(e)
+            );
+            if !za.is_zero() {
+                assert!(MontZn::from(za.inv()) == ma.inv());
+            }
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let x = MontZn::<Z127>::from(123456789usize);
+        let bytes = x.clone().serialize();
+        assert_eq!(bytes, Zn::<Z127>::from(123456789usize).serialize());
+        assert!(consume_deser::<MontZn<Z127>>(bytes).unwrap() == x);
+    }
+
+    pub struct Z47;
+    pub struct Z17;
+    pub struct Z127;
+
+    impl BigPrime for Z47 {
+        fn value() -> BigUint {
+            BigUint::from(47usize)
+        }
+    }
+
+    impl BigPrime for Z17 {
+        fn value() -> BigUint {
+            BigUint::from(17usize)
+        }
+    }
+
+    impl BigPrime for Z127 {
+        fn value() -> BigUint {
+            (BigUint::one() << 127) - BigUint::one()
+        }
+    }
+
+    montgomery_prime!(Z47, Z17, Z127);
+}
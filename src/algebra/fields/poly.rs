@@ -66,8 +66,17 @@ where
 impl<T, I> Field for PolyField<T, I>
 where
     I: Irreducible<T>,
-    Poly<T>: Integral,
+    Poly<T>: Integral + ConditionalSwap,
+{
+}
+
+impl<T, I> ConditionalSwap for PolyField<T, I>
+where
+    Poly<T>: ConditionalSwap,
 {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
 }
 
 impl<T, I> From<Poly<T>> for PolyField<T, I>
@@ -237,6 +246,7 @@ where
 {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
         self * rhs.inv()
     }
@@ -247,6 +257,7 @@ mod tests {
     use super::{Irreducible, PolyField};
     use crate::{algebra::{fields::zn::{BigPrime, Zn}, poly::Poly}, poly};
     use num_bigint::BigUint;
+    use num_traits::Zero;
 
     pub struct Z5;
     pub struct XcubePlus2;
@@ -263,5 +274,11 @@ mod tests {
         }
     }
 
-    type F = PolyField<Z5, XcubePlus2>;
+    type F = PolyField<Zn<Z5>, XcubePlus2>;
+
+    #[test]
+    fn reduce() {
+        assert!(F::from(poly![0, 0, 0, 1]) == F::from(poly![3]));
+        assert!((F::from(poly![4, 4, 4]) + F::from(poly![1, 1, 1])).is_zero());
+    }
 }
@@ -6,8 +6,8 @@ use rand::{distributions::Standard, prelude::Distribution};
 
 use crate::{
     algebra::{
-        algo::extended_gcd,
-        traits::{Field, FinGroup, Group, Ring, Sqrt},
+        algo::{extended_gcd, sqrt_mod},
+        traits::{ConditionalSwap, Field, FinGroup, Group, Ring, Sqrt},
     },
     bytes::{Deserialize, Serialize},
 };
@@ -34,6 +34,12 @@ impl<N: BigPrime> Ring for Zn<N> {}
 
 impl<N: BigPrime> Field for Zn<N> {}
 
+impl<N> ConditionalSwap for Zn<N> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
+}
+
 impl<N: BigPrime> From<BigUint> for Zn<N> {
     fn from(n: BigUint) -> Self {
         Self(n % N::value(), PhantomData)
@@ -90,13 +96,7 @@ impl<N> Eq for Zn<N> where BigUint: Eq {}
 
 impl<N: BigPrime> Sqrt for Zn<N> {
     fn sqrt(self) -> Option<Self> {
-        let deg = (N::value() + BigUint::one()) >> 2;
-        let sqrt = self.clone().pow(deg);
-        if sqrt.clone() * sqrt.clone() == self {
-            Some(Self::from(sqrt))
-        } else {
-            None
-        }
+        sqrt_mod(self, Self::one(), &N::value())
     }
 }
 
@@ -226,6 +226,8 @@ impl<N: BigPrime> Deserialize for Zn<N> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use num_bigint::BigUint;
     use num_traits::{Inv, Pow, Zero};
 
@@ -260,6 +262,30 @@ mod tests {
         assert!(sqrt.pow(BigUint::from(2usize)) == a);
     }
 
+    fn check_sqrt<N: BigPrime>() {
+        let p = N::value().to_u64_digits()[0];
+        let squares: HashSet<_> = (0..p).map(|x| x * x % p).collect();
+        for a in 0..p {
+            match Zn::<N>::from(a as usize).sqrt() {
+                Some(root) => {
+                    assert!(root.clone() * root == Zn::from(a as usize))
+                }
+                None => assert!(!squares.contains(&a)),
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_residue_classes() {
+        check_sqrt::<Z19>(); // 3 mod 8
+        check_sqrt::<Z23>(); // 7 mod 8
+        check_sqrt::<Z13>(); // 5 mod 8
+        check_sqrt::<Z29>();
+        check_sqrt::<Z17>(); // 1 mod 8
+        check_sqrt::<Z41>();
+        check_sqrt::<Z257>();
+    }
+
     pub struct Z74;
     pub struct Z180;
     pub struct Z47;
@@ -268,6 +294,11 @@ mod tests {
     pub struct Z18;
     pub struct Z17;
     pub struct Z19;
+    pub struct Z23;
+    pub struct Z13;
+    pub struct Z29;
+    pub struct Z41;
+    pub struct Z257;
 
     impl BigPrime for Z74 {
         fn value() -> BigUint {
@@ -316,4 +347,34 @@ mod tests {
             BigUint::from(19usize)
         }
     }
+
+    impl BigPrime for Z23 {
+        fn value() -> BigUint {
+            BigUint::from(23usize)
+        }
+    }
+
+    impl BigPrime for Z13 {
+        fn value() -> BigUint {
+            BigUint::from(13usize)
+        }
+    }
+
+    impl BigPrime for Z29 {
+        fn value() -> BigUint {
+            BigUint::from(29usize)
+        }
+    }
+
+    impl BigPrime for Z41 {
+        fn value() -> BigUint {
+            BigUint::from(41usize)
+        }
+    }
+
+    impl BigPrime for Z257 {
+        fn value() -> BigUint {
+            BigUint::from(257usize)
+        }
+    }
 }
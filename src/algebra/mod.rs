@@ -1,6 +1,15 @@
 pub mod algo;
+pub mod binary_curve;
+pub mod coordinates;
 pub mod curve;
+pub mod dyn_curve;
+pub mod edwards;
 pub mod fields;
+pub mod montgomery_curve;
 pub mod poly;
+pub mod primality;
+pub mod scalar;
+pub mod sec1;
 pub mod traits;
+pub mod validation;
 pub mod zp;
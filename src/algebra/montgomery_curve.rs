@@ -0,0 +1,145 @@
+use num_bigint::{BigInt, BigUint};
+
+use super::traits::{Field, FinGroup};
+
+/// Curve in Montgomery form `B * y^2 = x^3 + A * x^2 + x`.
+///
+/// Points are handled through their `x` coordinate only, which is enough
+/// for Diffie-Hellman: `x(k * P)` depends only on `k` and `x(P)`.
+pub trait MontgomeryCurve<F: Field> {
+    fn a() -> F;
+
+    /// Not needed by the ladder, which works on the twist as well.
+    fn b() -> F {
+        F::one()
+    }
+
+    /// `(A - 2) / 4`, the constant used in ladder doublings.
+    fn a24() -> F {
+        (Self::a() - F::one() * BigInt::from(2)) / (F::one() * BigInt::from(4))
+    }
+
+    /// `x(k * P)` from `x(P)` by the Montgomery ladder of RFC 7748.
+    ///
+    /// Performs exactly `bits` steps, so `scalar` must fit into `bits` bits,
+    /// and exchanges the two accumulators with `cswap` rather than by
+    /// indexing with the bits of `scalar`. The point at infinity is mapped
+    /// to `0`.
+    fn ladder(u: F, scalar: &BigUint, bits: u64) -> F
+    where
+        F: FinGroup,
+    {
+        debug_assert!(scalar.bits() <= bits);
+        let a24 = Self::a24();
+        let mut x2 = (F::one(), F::zero());
+        let mut x3 = (u.clone(), F::one());
+        let mut swap = 0;
+        for i in (0..bits).rev() {
+            let bit = scalar.bit(i) as u64;
+            swap ^= bit;
+            cswap(swap, &mut x2, &mut x3);
+            swap = bit;
+            x3 = differential_add(&x2, &x3, &u);
+            x2 = double(&x2, &a24);
+        }
+        cswap(swap, &mut x2, &mut x3);
+        let (x, z) = x2;
+        x * z.pow(F::order() - 2u32)
+    }
+}
+
+/// `cswap` of RFC 7748: exchanges `a` and `b` if `swap` is 1.
+fn cswap<F: Field>(swap: u64, (xa, za): &mut (F, F), (xb, zb): &mut (F, F)) {
+    let mask = swap.wrapping_neg();
+    xa.conditional_swap(xb, mask);
+    za.conditional_swap(zb, mask);
+}
+
+/// `P + Q` given `P - Q = (u : 1)`.
+fn differential_add<F: Field>(
+    (x2, z2): &(F, F),
+    (x3, z3): &(F, F),
+    u: &F,
+) -> (F, F) {
+    let da = (x3.clone() - z3.clone()) * (x2.clone() + z2.clone());
+    let cb = (x3.clone() + z3.clone()) * (x2.clone() - z2.clone());
+    let sum = da.clone() + cb.clone();
+    let diff = da - cb;
+    (sum.clone() * sum, u.clone() * diff.clone() * diff)
+}
+
+fn double<F: Field>((x, z): &(F, F), a24: &F) -> (F, F) {
+    let aa = sq(x.clone() + z.clone());
+    let bb = sq(x.clone() - z.clone());
+    let e = aa.clone() - bb.clone();
+    (aa.clone() * bb, e.clone() * (aa + a24.clone() * e))
+}
+
+fn sq<F: Field>(x: F) -> F {
+    x.clone() * x
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+    use rand::{thread_rng, Rng};
+
+    use super::MontgomeryCurve;
+    use crate::algebra::{
+        curve::{Curve, EllipticPoint},
+        fields::zn::{BigPrime, Zn},
+    };
+
+    type F = Zn<P10007>;
+
+    #[test]
+    fn matches_weierstrass() {
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let point: EllipticPoint<F, Weierstrass> = rng.gen();
+            let (x, _) = point.clone().into();
+            let k = rng.gen::<u16>();
+            let expected = match (point * BigInt::from(k)).into() {
+                Some((x, _)) => x - F::from(1),
+                None => F::zero(),
+            };
+            let u = x - F::from(1);
+            assert!(Toy::ladder(u, &BigUint::from(k), 16) == expected);
+        }
+    }
+
+    /// `y^2 = x^3 + 3 * x^2 + x`.
+    struct Toy;
+
+    impl MontgomeryCurve<F> for Toy {
+        fn a() -> F {
+            F::from(3)
+        }
+    }
+
+    /// [`Toy`] shifted by `x -> x + 1`, which removes the square term.
+    struct Weierstrass;
+
+    impl Curve<F> for Weierstrass {
+        fn group_order() -> BigUint {
+            BigUint::from(10007u32)
+        }
+
+        fn a() -> F {
+            -F::from(2)
+        }
+
+        fn b() -> F {
+            F::from(1)
+        }
+    }
+
+    pub struct P10007;
+
+    impl BigPrime for P10007 {
+        fn value() -> BigUint {
+            BigUint::from(10007usize)
+        }
+    }
+}
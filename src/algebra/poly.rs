@@ -1,5 +1,6 @@
 use std::{
     iter::{repeat_with, Product, Sum},
+    mem::take,
     ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
@@ -13,7 +14,7 @@ use rand::{distributions::Standard, prelude::Distribution, Error, Fill, Rng};
 
 use super::{
     algo::repeat_monoid,
-    traits::{Field, Group, Ring},
+    traits::{ConditionalSwap, Field, Group, Ring},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -53,7 +54,7 @@ impl<T> Poly<T> {
         let mut reversed_trimmed = self
             .0
             .into_iter()
-            .zip_longest(rhs.0.into_iter())
+            .zip_longest(rhs.0)
             .map(op)
             .rev()
             .skip_while(T::is_zero)
@@ -120,6 +121,21 @@ impl<T: Zero> From<Vec<Monome<T>>> for Poly<T> {
     }
 }
 
+/// Coefficients are padded to the longer polynomial, so only degrees leak.
+impl<T: ConditionalSwap + Zero> ConditionalSwap for Poly<T> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let len = self.0.len().max(other.0.len());
+        let (mut a, mut b) = (take(&mut self.0), take(&mut other.0));
+        a.resize_with(len, T::zero);
+        b.resize_with(len, T::zero);
+        for (x, y) in a.iter_mut().zip(&mut b) {
+            x.conditional_swap(y, mask);
+        }
+        *self = a.into();
+        *other = b.into();
+    }
+}
+
 impl<T> From<Poly<T>> for Vec<T> {
     fn from(poly: Poly<T>) -> Self {
         poly.0
@@ -243,7 +259,7 @@ impl<T> Zero for Poly<T> where Self: Add<Output = Self> {
     }
 
     fn is_zero(&self) -> bool {
-        self.0.len() == 0
+        self.0.is_empty()
     }
 }
 
@@ -0,0 +1,259 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, ToPrimitive, Zero};
+use rand::RngCore;
+
+/// Bases that make Miller-Rabin exact below 3.3 * 10^24, which covers u64.
+const U64_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Deterministic Miller-Rabin for 64-bit inputs.
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &U64_BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    let pow = |mut base: u64, mut exp: u64| {
+        let mut result = 1;
+        while exp != 0 {
+            if exp & 1 == 1 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exp >>= 1;
+        }
+        result
+    };
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    U64_BASES.iter().all(|&a| {
+        let mut x = pow(a, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Whether odd `n > 3` is a strong probable prime to `base`.
+pub fn strong_probable_prime(n: &BigUint, base: &BigUint) -> bool {
+    let minus_one = n - 1u32;
+    let s = minus_one.trailing_zeros().unwrap();
+    let mut x = base.modpow(&(&minus_one >> s), n);
+    if x.is_one() || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Miller-Rabin with `rounds` random bases.
+///
+/// A composite passes with probability at most `4^-rounds`.
+pub fn miller_rabin(n: &BigUint, rounds: usize, rng: &mut dyn RngCore) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime_u64(n);
+    }
+    if !n.bit(0) {
+        return false;
+    }
+    let (low, high) = (BigUint::from(2u32), n - 1u32);
+    (0..rounds)
+        .all(|_| strong_probable_prime(n, &rng.gen_biguint_range(&low, &high)))
+}
+
+/// Jacobi symbol `(a / n)` for odd `n`.
+pub fn jacobi(a: &BigUint, n: &BigUint) -> i8 {
+    assert!(n.bit(0), "Jacobi symbol needs an odd modulus");
+    let (mut a, mut n) = (a % n, n.clone());
+    let mut result = 1;
+    while !a.is_zero() {
+        let zeros = a.trailing_zeros().unwrap();
+        a >>= zeros;
+        if zeros & 1 == 1 && matches!(low_bits(&n) & 7, 3 | 5) {
+            result = -result;
+        }
+        std::mem::swap(&mut a, &mut n);
+        if low_bits(&a) & 3 == 3 && low_bits(&n) & 3 == 3 {
+            result = -result;
+        }
+        a %= &n;
+    }
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+fn low_bits(n: &BigUint) -> u64 {
+    n.iter_u64_digits().next().unwrap_or(0)
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters.
+///
+/// Expects an odd `n > 3` that is not a perfect square.
+pub fn strong_lucas(n: &BigUint) -> bool {
+    // D is the first of 5, -7, 9, -11, ... with (D / n) = -1.
+    let mut d = 5u64;
+    let mut negative = false;
+    let d = loop {
+        let residue = if negative {
+            n - BigUint::from(d) % n
+        } else {
+            BigUint::from(d)
+        };
+        match jacobi(&residue, n) {
+            -1 => break residue,
+            0 if BigUint::from(d) != *n => return false,
+            _ => {}
+        }
+        d += 2;
+        negative = !negative;
+    };
+    // P = 1 and Q = (1 - D) / 4, all kept modulo n.
+    let half = |x: BigUint| if x.bit(0) { (x + n) >> 1 } else { x >> 1 };
+    let q = (n + 1u32 - &d) % n * half(half(BigUint::one())) % n;
+    let sub = |a: &BigUint, b: &BigUint| (a + n - b % n) % n;
+
+    let plus_one = n + 1u32;
+    let s = plus_one.trailing_zeros().unwrap();
+    let k = &plus_one >> s;
+    let (mut u, mut v, mut q_k) = (BigUint::one(), BigUint::one(), q.clone());
+    for i in (0..k.bits() - 1).rev() {
+        u = &u * &v % n;
+        v = sub(&(&v * &v), &(&q_k << 1));
+        q_k = &q_k * &q_k % n;
+        if k.bit(i) {
+            let (u_old, v_old) = (u, v);
+            u = half(&u_old + &v_old) % n;
+            v = half(&d * &u_old + &v_old) % n;
+            q_k = &q_k * &q % n;
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = sub(&(&v * &v), &(&q_k << 1));
+        q_k = &q_k * &q_k % n;
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Baillie-PSW: Miller-Rabin to base 2 followed by a strong Lucas test.
+///
+/// No composite is known to pass, and none exists below 2^64.
+pub fn baillie_psw(n: &BigUint) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime_u64(n);
+    }
+    if U64_BASES.iter().any(|&p| (n % p).is_zero()) {
+        return false;
+    }
+    let root = n.sqrt();
+    strong_probable_prime(n, &BigUint::from(2u32))
+        && &root * &root != *n
+        && strong_lucas(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::One;
+    use rand::thread_rng;
+
+    use super::{
+        baillie_psw, is_prime_u64, jacobi, miller_rabin, strong_lucas,
+        strong_probable_prime,
+    };
+
+    fn trial_division(n: u64) -> bool {
+        n > 1 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn small() {
+        for n in 0..20000 {
+            assert_eq!(is_prime_u64(n), trial_division(n));
+            assert_eq!(baillie_psw(&BigUint::from(n)), trial_division(n));
+        }
+    }
+
+    #[test]
+    fn pseudoprimes() {
+        let two = BigUint::from(2u32);
+        // Carmichael numbers and strong pseudoprimes to base 2.
+        for &n in &[561u64, 41041, 825265, 2047, 3277, 4033, 3215031751] {
+            assert!(!is_prime_u64(n));
+        }
+        for &n in &[2047u32, 3277, 4033, 4681, 8321] {
+            assert!(strong_probable_prime(&BigUint::from(n), &two));
+            assert!(!strong_lucas(&BigUint::from(n)));
+        }
+        // Strong Lucas pseudoprimes.
+        for &n in &[5459u32, 5777, 10877, 16109, 18971, 22499] {
+            assert!(strong_lucas(&BigUint::from(n)));
+            assert!(!strong_probable_prime(&BigUint::from(n), &two));
+        }
+    }
+
+    #[test]
+    fn jacobi_symbol() {
+        for n in (3..200u64).step_by(2).filter(|&n| trial_division(n)) {
+            let n_big = BigUint::from(n);
+            for a in 0..n {
+                let expected = if a == 0 {
+                    0
+                } else if (1..n).any(|x| x * x % n == a) {
+                    1
+                } else {
+                    -1
+                };
+                assert_eq!(jacobi(&BigUint::from(a), &n_big), expected);
+            }
+        }
+        assert_eq!(
+            jacobi(&BigUint::from(1001u32), &BigUint::from(9907u32)),
+            -1
+        );
+        assert_eq!(jacobi(&BigUint::from(19u32), &BigUint::from(45u32)), 1);
+        assert_eq!(jacobi(&BigUint::from(8u32), &BigUint::from(21u32)), -1);
+        assert_eq!(jacobi(&BigUint::from(5u32), &BigUint::from(21u32)), 1);
+    }
+
+    #[test]
+    fn large() {
+        let mut rng = thread_rng();
+        let mersenne = |e: usize| (BigUint::one() << e) - 1u32;
+        for &e in &[61, 89, 107, 127, 521, 607] {
+            assert!(baillie_psw(&mersenne(e)));
+            assert!(miller_rabin(&mersenne(e), 20, &mut rng));
+        }
+        for &e in &[67, 101, 256, 523] {
+            assert!(!baillie_psw(&mersenne(e)));
+            assert!(!miller_rabin(&mersenne(e), 20, &mut rng));
+        }
+        let product = mersenne(61) * mersenne(89);
+        assert!(!baillie_psw(&product));
+        assert!(!miller_rabin(&product, 20, &mut rng));
+        assert!(!baillie_psw(&(mersenne(127) * mersenne(127))));
+    }
+}
@@ -0,0 +1,321 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{ToPrimitive, Zero};
+
+use super::traits::{reduce_scalar, DynFinGroup, Group};
+
+/// Window size for tables built without a specific memory budget.
+pub const DEFAULT_WINDOW: usize = 4;
+
+/// Width-`width` non-adjacent form of `scalar`, least significant digit
+/// first. Nonzero digits are odd, below `2^(width - 1)` by absolute value,
+/// and followed by at least `width - 1` zeros.
+pub fn wnaf(scalar: &BigInt, width: usize) -> Vec<i64> {
+    assert!((2..=32).contains(&width));
+    let modulus = 1i64 << width;
+    let mut k = scalar.magnitude().clone();
+    let mut digits = Vec::with_capacity(k.bits() as usize + 1);
+    while !k.is_zero() {
+        let digit = if k.bit(0) {
+            let low = (&k % BigUint::from(modulus as u64)).to_i64().unwrap();
+            let digit = if low >= modulus / 2 {
+                low - modulus
+            } else {
+                low
+            };
+            if digit > 0 {
+                k -= BigUint::from(digit as u64);
+            } else {
+                k += BigUint::from((-digit) as u64);
+            }
+            digit
+        } else {
+            0
+        };
+        digits.push(digit);
+        k >>= 1;
+    }
+    if scalar.sign() == Sign::Minus {
+        digits.iter_mut().for_each(|digit| *digit = -*digit);
+    }
+    digits
+}
+
+/// Variable-base multiplication using the width-`width` NAF of `scalar`.
+pub fn mul_wnaf<T: Group>(point: T, scalar: &BigInt, width: usize) -> T {
+    let digits = wnaf(scalar, width);
+    let double = point.clone().double();
+    let mut odd = vec![point];
+    for i in 1..1 << (width - 2) {
+        odd.push(odd[i - 1].clone() + double.clone());
+    }
+    let mut result = T::zero();
+    for &digit in digits.iter().rev() {
+        result = result.double();
+        if digit > 0 {
+            result = result + odd[(digit / 2) as usize].clone();
+        } else if digit < 0 {
+            result = result - odd[(-digit / 2) as usize].clone();
+        }
+    }
+    result
+}
+
+/// Interleaved wNAF multiplication: one shared doubling chain for all
+/// points. Best for a handful of points.
+pub fn straus<T: Group>(points: &[T], scalars: &[BigInt], width: usize) -> T {
+    assert_eq!(points.len(), scalars.len());
+    let digits: Vec<_> = scalars.iter().map(|k| wnaf(k, width)).collect();
+    let odd: Vec<Vec<T>> = points
+        .iter()
+        .map(|point| {
+            let double = point.clone().double();
+            let mut odd = vec![point.clone()];
+            for i in 1..1 << (width - 2) {
+                odd.push(odd[i - 1].clone() + double.clone());
+            }
+            odd
+        })
+        .collect();
+    let len = digits.iter().map(Vec::len).max().unwrap_or(0);
+    let mut result = T::zero();
+    for i in (0..len).rev() {
+        result = result.double();
+        for (digits, odd) in digits.iter().zip(&odd) {
+            match digits.get(i) {
+                Some(&d) if d > 0 => {
+                    result = result + odd[d as usize / 2].clone()
+                }
+                Some(&d) if d < 0 => {
+                    result = result - odd[(-d) as usize / 2].clone()
+                }
+                _ => {}
+            }
+        }
+    }
+    result
+}
+
+/// Bucket method: each `window`-bit slice of the scalars sorts the points
+/// into buckets, which are then summed with a running total. Best for many
+/// points.
+pub fn pippenger<T: Group>(
+    points: &[T],
+    scalars: &[BigInt],
+    window: usize,
+) -> T {
+    assert_eq!(points.len(), scalars.len());
+    assert!((1..=24).contains(&window));
+    let (points, scalars): (Vec<_>, Vec<_>) = points
+        .iter()
+        .zip(scalars)
+        .map(|(point, k)| match k.sign() {
+            Sign::Minus => (-point.clone(), k.magnitude()),
+            _ => (point.clone(), k.magnitude()),
+        })
+        .unzip();
+    let bits = scalars.iter().map(|k| k.bits()).max().unwrap_or(0) as usize;
+    let mut result = T::zero();
+    for low in (0..bits.div_ceil(window)).rev().map(|i| i * window) {
+        for _ in 0..window {
+            result = result.double();
+        }
+        let mut buckets = vec![T::zero(); (1 << window) - 1];
+        for (point, k) in points.iter().zip(&scalars) {
+            let digit = (0..window)
+                .rev()
+                .fold(0, |acc, j| acc << 1 | k.bit((low + j) as u64) as usize);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].clone() + point.clone();
+            }
+        }
+        let mut running = T::zero();
+        let mut window_sum = T::zero();
+        for bucket in buckets.into_iter().rev() {
+            running = running + bucket;
+            window_sum = window_sum + running.clone();
+        }
+        result = result + window_sum;
+    }
+    result
+}
+
+/// Splits `scalar` into `(k1, k2)` with `k1 + k2 * lambda = scalar` modulo
+/// `order`, given a reduced basis `(a1, b1), (a2, b2)` of the lattice of
+/// pairs `(i, j)` with `i + j * lambda = 0`. Both halves have about half the
+/// bit length of `order`.
+pub fn glv_decompose(
+    scalar: &BigInt,
+    order: &BigUint,
+    [(a1, b1), (a2, b2)]: &[(BigInt, BigInt); 2],
+) -> (BigInt, BigInt) {
+    let k = BigInt::from(reduce_scalar(scalar, order));
+    let n = BigInt::from(order.clone());
+    let c1 = round_div(b2 * &k, &n);
+    let c2 = round_div(-b1 * &k, &n);
+    let k1 = k - &c1 * a1 - &c2 * a2;
+    let k2 = -c1 * b1 - c2 * b2;
+    (k1, k2)
+}
+
+/// `value / divisor` rounded to the nearest integer, `divisor > 0`.
+fn round_div(value: BigInt, divisor: &BigInt) -> BigInt {
+    let twice = divisor * 2u32;
+    let shifted = value * 2u32 + divisor;
+    let quotient = &shifted / &twice;
+    if shifted.sign() == Sign::Minus && !(shifted % twice).is_zero() {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// Multiples of a fixed base point for fast repeated multiplication.
+///
+/// Row `i` holds `j * 2^(window * i) * base` for every `window`-bit `j`, so
+/// a product costs one addition per window and no doublings.
+pub struct FixedBaseTable<T> {
+    window: usize,
+    order: BigUint,
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: DynFinGroup> FixedBaseTable<T> {
+    pub fn new(base: T, window: usize) -> Self {
+        assert!((1..=16).contains(&window));
+        let order = base.group_order();
+        let bits = order.bits() as usize;
+        let mut rows = Vec::with_capacity(bits.div_ceil(window));
+        let mut row_base = base;
+        for _ in 0..rows.capacity() {
+            let mut row = Vec::with_capacity(1 << window);
+            row.push(T::zero());
+            for j in 1..1 << window {
+                row.push(row[j - 1].clone() + row_base.clone());
+            }
+            for _ in 0..window {
+                row_base = row_base.double();
+            }
+            rows.push(row);
+        }
+        Self {
+            window,
+            order,
+            rows,
+        }
+    }
+
+    /// The point this table multiplies.
+    pub fn base(&self) -> &T {
+        &self.rows[0][1]
+    }
+
+    /// Every entry of a row is read and the right one is selected with a
+    /// conditional swap, so memory accesses do not depend on the scalar.
+    /// Additions are only as constant-time as those of `T`.
+    pub fn mul(&self, scalar: &BigInt) -> T {
+        let scalar = reduce_scalar(scalar, &self.order);
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let low = (i * self.window) as u64;
+                let digit = (0..self.window as u64)
+                    .rev()
+                    .fold(0, |acc, j| acc << 1 | scalar.bit(low + j) as u64);
+                select(row, digit)
+            })
+            .fold(T::zero(), T::add)
+    }
+}
+
+/// `row[index]` found by scanning the whole row.
+fn select<T: DynFinGroup>(row: &[T], index: u64) -> T {
+    let mut result = T::zero();
+    for (j, entry) in row.iter().enumerate() {
+        let diff = j as u64 ^ index;
+        let mask = ((diff | diff.wrapping_neg()) >> 63).wrapping_sub(1);
+        result.conditional_swap(&mut entry.clone(), mask);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint, RandBigInt};
+    use num_traits::{One, Zero};
+    use rand::thread_rng;
+
+    use super::{mul_wnaf, pippenger, straus, wnaf, FixedBaseTable};
+    use crate::algebra::fields::zn::{BigPrime, Zn};
+
+    #[test]
+    fn wnaf_digits() {
+        let mut rng = thread_rng();
+        for width in 2..8 {
+            for _ in 0..100 {
+                let k = rng.gen_bigint(200);
+                let digits = wnaf(&k, width);
+                let value =
+                    digits.iter().rev().fold(BigInt::zero(), |acc, &d| {
+                        acc * BigInt::from(2) + BigInt::from(d)
+                    });
+                assert_eq!(value, k);
+                for (i, &d) in digits.iter().enumerate() {
+                    if d != 0 {
+                        assert!(d % 2 != 0 && d.abs() < 1 << (width - 1));
+                        let next = &digits[i + 1..digits.len().min(i + width)];
+                        assert!(next.iter().all(|&d| d == 0));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wnaf_mul() {
+        let mut rng = thread_rng();
+        for width in 2..7 {
+            for _ in 0..100 {
+                let k = rng.gen_bigint(100);
+                assert_eq!(mul_wnaf(BigInt::one(), &k, width), k);
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_base() {
+        let mut rng = thread_rng();
+        for window in 1..6 {
+            let table = FixedBaseTable::new(Zn::<Z1009>::from(5), window);
+            for _ in 0..100 {
+                let k = rng.gen_bigint(20);
+                assert!(table.mul(&k) == Zn::from(k * BigInt::from(5)));
+            }
+        }
+    }
+
+    #[test]
+    fn multi_scalar() {
+        let mut rng = thread_rng();
+        for n in [0, 1, 2, 7, 40] {
+            let points: Vec<_> = (0..n).map(|_| rng.gen_bigint(30)).collect();
+            let scalars: Vec<_> = (0..n).map(|_| rng.gen_bigint(100)).collect();
+            let expected: BigInt =
+                points.iter().zip(&scalars).map(|(p, k)| p * k).sum();
+            for width in 2..6 {
+                assert_eq!(straus(&points, &scalars, width), expected);
+            }
+            for window in 1..9 {
+                assert_eq!(pippenger(&points, &scalars, window), expected);
+            }
+        }
+    }
+
+    pub struct Z1009;
+
+    impl BigPrime for Z1009 {
+        fn value() -> BigUint {
+            BigUint::from(1009usize)
+        }
+    }
+}
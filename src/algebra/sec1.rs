@@ -0,0 +1,192 @@
+//! Point encodings from SEC 1, section 2.3.3: `0x00` for the point at
+//! infinity, `0x02` or `0x03` followed by `x` for compressed points, and
+//! `0x04` followed by `x` and `y` otherwise. Coordinates are big-endian and
+//! padded to the byte length of the field modulus.
+
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use super::curve::NotOnCurve;
+
+const INFINITY: u8 = 0x00;
+const EVEN: u8 = 0x02;
+const ODD: u8 = 0x03;
+const UNCOMPRESSED: u8 = 0x04;
+
+/// Decoded point whose coordinates are not yet checked against a curve.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Sec1Point {
+    Infinity,
+    Compressed { x: BigUint, odd: bool },
+    Uncompressed { x: BigUint, y: BigUint },
+}
+
+#[derive(Debug, Error)]
+pub enum Sec1Error {
+    #[error("Not enough bytes")]
+    NotEnoughBytes,
+    #[error("Unknown point tag {0:#04x}")]
+    UnknownTag(u8),
+    #[error("Coordinate is not below the field modulus")]
+    NotInField,
+    #[error("Odd y requested where y is zero")]
+    WrongParity,
+    #[error(transparent)]
+    NotOnCurve(#[from] NotOnCurve),
+}
+
+/// Byte length of coordinates modulo `modulus`.
+pub fn field_len(modulus: &BigUint) -> usize {
+    (modulus.bits() as usize).div_ceil(8)
+}
+
+pub fn encode(
+    coords: Option<(BigUint, BigUint)>,
+    len: usize,
+    compressed: bool,
+) -> Vec<u8> {
+    let (x, y) = match coords {
+        Some(coords) => coords,
+        None => return vec![INFINITY],
+    };
+    let mut result = if compressed {
+        vec![if y.bit(0) { ODD } else { EVEN }]
+    } else {
+        vec![UNCOMPRESSED]
+    };
+    result.append(&mut to_bytes(&x, len));
+    if !compressed {
+        result.append(&mut to_bytes(&y, len));
+    }
+    result
+}
+
+/// Reads one encoding with `len`-byte coordinates, `None` if the stream is
+/// empty.
+pub fn read(
+    stream: &mut impl Iterator<Item = u8>,
+    len: usize,
+) -> Result<Option<Sec1Point>, Sec1Error> {
+    let tag = match stream.next() {
+        Some(tag) => tag,
+        None => return Ok(None),
+    };
+    let mut coordinate = || {
+        let bytes: Vec<_> = stream.by_ref().take(len).collect();
+        if bytes.len() == len {
+            Ok(BigUint::from_bytes_be(&bytes))
+        } else {
+            Err(Sec1Error::NotEnoughBytes)
+        }
+    };
+    let point = match tag {
+        INFINITY => Sec1Point::Infinity,
+        EVEN | ODD => Sec1Point::Compressed {
+            x: coordinate()?,
+            odd: tag == ODD,
+        },
+        UNCOMPRESSED => {
+            let x = coordinate()?;
+            let y = coordinate()?;
+            Sec1Point::Uncompressed { x, y }
+        }
+        tag => return Err(Sec1Error::UnknownTag(tag)),
+    };
+    Ok(Some(point))
+}
+
+fn to_bytes(value: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut result = vec![0; len - bytes.len()];
+    result.extend(bytes);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::{encode, field_len, read, Sec1Error, Sec1Point};
+    use crate::{
+        algebra::{
+            curve::{Curve, EllipticPoint},
+            fields::zn::{BigPrime, Zn},
+        },
+        bytes::{consume_deser, ConsumeDeserError},
+    };
+
+    #[test]
+    fn round_trip() {
+        let len = field_len(&BigUint::from(65521u32));
+        assert_eq!(len, 2);
+        let (x, y) = (BigUint::from(0x1234u32), BigUint::from(7u32));
+        let coords = Some((x.clone(), y.clone()));
+        let uncompressed = encode(coords.clone(), len, false);
+        assert_eq!(uncompressed, vec![4, 0x12, 0x34, 0, 7]);
+        let compressed = encode(coords, len, true);
+        assert_eq!(compressed, vec![3, 0x12, 0x34]);
+        assert_eq!(encode(None, len, true), vec![0]);
+        let mut stream = uncompressed.into_iter().chain(compressed).chain([0]);
+        let points = [
+            Sec1Point::Uncompressed { x: x.clone(), y },
+            Sec1Point::Compressed { x, odd: true },
+            Sec1Point::Infinity,
+        ];
+        for point in points.iter() {
+            assert_eq!(read(&mut stream, len).unwrap().as_ref(), Some(point));
+        }
+        assert_eq!(read(&mut stream, len).unwrap(), None);
+    }
+
+    #[test]
+    fn malformed() {
+        let read = |bytes: Vec<u8>| read(&mut bytes.into_iter(), 2);
+        assert!(matches!(read(vec![5, 0, 0]), Err(Sec1Error::UnknownTag(5))));
+        assert!(matches!(read(vec![2, 0]), Err(Sec1Error::NotEnoughBytes)));
+        assert!(matches!(
+            read(vec![4, 0, 1, 0]),
+            Err(Sec1Error::NotEnoughBytes)
+        ));
+    }
+
+    /// SEC1 has no odd `y` for the point `(0, 0)`.
+    #[test]
+    fn compressed_zero_y() {
+        type Point = EllipticPoint<Zn<P10037>, Origin>;
+        let origin = Origin::affine(Zn::from(0), Zn::from(0)).unwrap();
+        assert!(consume_deser::<Point>([2, 0, 0]).unwrap() == origin);
+        assert!(matches!(
+            consume_deser::<Point>([3, 0, 0]),
+            Err(ConsumeDeserError::Deserialization(Sec1Error::WrongParity))
+        ));
+    }
+
+    /// `y^2 = x^3 + 4x` of order `8 * 1277`.
+    struct Origin;
+
+    impl Curve<Zn<P10037>> for Origin {
+        fn group_order() -> BigUint {
+            BigUint::from(1277usize)
+        }
+
+        fn cofactor() -> BigUint {
+            BigUint::from(8usize)
+        }
+
+        fn a() -> Zn<P10037> {
+            Zn::from(4)
+        }
+
+        fn b() -> Zn<P10037> {
+            Zn::from(0)
+        }
+    }
+
+    struct P10037;
+
+    impl BigPrime for P10037 {
+        fn value() -> BigUint {
+            BigUint::from(10037usize)
+        }
+    }
+}
@@ -3,9 +3,14 @@ use std::{
     ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
-use num_bigint::{BigInt, BigUint};
+use num_bigint::{BigInt, BigUint, Sign};
 use num_traits::{Inv, One, Pow, Zero};
 
+use super::{
+    algo::montgomery_ladder,
+    scalar::{pippenger, straus},
+};
+
 pub trait Value: Clone + Eq {}
 
 pub trait BasicGroup:
@@ -16,10 +21,69 @@ pub trait BasicGroup:
 pub trait Group:
     BasicGroup + Sub<Output = Self> + Mul<BigInt, Output = Self> + Sum
 {
+    fn double(self) -> Self {
+        self.clone() + self
+    }
 }
 
-pub trait FinGroup: Group {
+pub trait FinGroup: Group + ConditionalSwap {
     fn order() -> BigUint;
+
+    /// Multiplication by a secret scalar.
+    ///
+    /// Runs a Montgomery ladder padded to the bit length of the group order,
+    /// so the sequence of group operations and swaps does not depend on the
+    /// scalar. Reducing the scalar is not constant-time, and neither is the
+    /// arithmetic of types backed by [`BigUint`], whose length leaks.
+    fn mul_secret(self, scalar: &BigInt) -> Self {
+        let order = Self::order();
+        let scalar = reduce_scalar(scalar, &order);
+        montgomery_ladder(Self::add, &scalar, order.bits(), self, Self::zero())
+    }
+}
+
+/// Finite group whose order is known from its elements rather than its
+/// type, like the points of a curve chosen at runtime.
+pub trait DynFinGroup: Group + ConditionalSwap {
+    fn group_order(&self) -> BigUint;
+
+    /// Multiplication by a secret scalar, see [`FinGroup::mul_secret`].
+    fn mul_secret(self, scalar: &BigInt) -> Self;
+}
+
+impl<T: FinGroup> DynFinGroup for T {
+    fn group_order(&self) -> BigUint {
+        T::order()
+    }
+
+    fn mul_secret(self, scalar: &BigInt) -> Self {
+        FinGroup::mul_secret(self, scalar)
+    }
+}
+
+/// Sum of `scalars[i] * points[i]` over all `i`.
+///
+/// Uses interleaved wNAF for a few points and Pippenger's bucket method once
+/// there are enough of them to fill the buckets.
+pub fn multi_scalar_mul<T: Group>(points: &[T], scalars: &[BigInt]) -> T {
+    assert_eq!(points.len(), scalars.len());
+    match points.len() {
+        0..=31 => straus(points, scalars, 5),
+        n => {
+            let log = (usize::BITS - n.leading_zeros()) as usize;
+            pippenger(points, scalars, log - 2)
+        }
+    }
+}
+
+/// Representative of `scalar` modulo `order` in `[0, order)`.
+pub fn reduce_scalar(scalar: &BigInt, order: &BigUint) -> BigUint {
+    let order = BigInt::from(order.clone());
+    let mut scalar = scalar % &order;
+    if scalar.sign() == Sign::Minus {
+        scalar += order;
+    }
+    scalar.magnitude().clone()
 }
 
 pub trait Ring:
@@ -29,7 +93,17 @@ pub trait Ring:
 
 pub trait Integral: Ring + Div<Output = Self> + Rem<Output = Self> {}
 
-pub trait Field: Ring + Inv<Output = Self> + Div<Output = Self> {}
+pub trait Field:
+    Ring + Inv<Output = Self> + Div<Output = Self> + ConditionalSwap
+{
+}
+
+/// Exchange of two values decided by a mask rather than by a branch.
+pub trait ConditionalSwap {
+    /// Swaps `self` and `other` if `mask` is all ones, keeps them if it is
+    /// zero. No other mask is allowed.
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64);
+}
 
 pub trait Sqrt: Sized {
     fn sqrt(self) -> Option<Self>;
@@ -47,3 +121,87 @@ impl<T> Integral for T where T: Ring + Div<Output = Self> + Rem<Output = Self> {
 impl Group for BigInt {}
 
 impl Ring for BigInt {}
+
+impl ConditionalSwap for [u64] {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        debug_assert_eq!(self.len(), other.len());
+        for (a, b) in self.iter_mut().zip(other) {
+            let t = (*a ^ *b) & mask;
+            *a ^= t;
+            *b ^= t;
+        }
+    }
+}
+
+impl<const L: usize> ConditionalSwap for [u64; L] {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self[..].conditional_swap(other, mask);
+    }
+}
+
+/// Digits are padded to the longer value, so only the lengths leak.
+impl ConditionalSwap for BigUint {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let (mut a, mut b) = (self.to_u64_digits(), other.to_u64_digits());
+        let len = a.len().max(b.len());
+        a.resize(len, 0);
+        b.resize(len, 0);
+        a.conditional_swap(&mut b, mask);
+        *self = from_u64_digits(&a);
+        *other = from_u64_digits(&b);
+    }
+}
+
+impl ConditionalSwap for BigInt {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let sign = |x: &BigInt| [(x.sign() == Sign::Minus) as u64];
+        let (mut a_sign, mut b_sign) = (sign(self), sign(other));
+        a_sign.conditional_swap(&mut b_sign, mask);
+        let (mut a, mut b) =
+            (self.magnitude().clone(), other.magnitude().clone());
+        a.conditional_swap(&mut b, mask);
+        let signed = |minus: [u64; 1], magnitude| {
+            let sign = if minus[0] == 1 {
+                Sign::Minus
+            } else {
+                Sign::Plus
+            };
+            BigInt::from_biguint(sign, magnitude)
+        };
+        *self = signed(a_sign, a);
+        *other = signed(b_sign, b);
+    }
+}
+
+impl<A, B, C> ConditionalSwap for (A, B, C)
+where
+    A: ConditionalSwap,
+    B: ConditionalSwap,
+    C: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+        self.1.conditional_swap(&mut other.1, mask);
+        self.2.conditional_swap(&mut other.2, mask);
+    }
+}
+
+impl<A, B, C, D> ConditionalSwap for (A, B, C, D)
+where
+    A: ConditionalSwap,
+    B: ConditionalSwap,
+    C: ConditionalSwap,
+    D: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+        self.1.conditional_swap(&mut other.1, mask);
+        self.2.conditional_swap(&mut other.2, mask);
+        self.3.conditional_swap(&mut other.3, mask);
+    }
+}
+
+fn from_u64_digits(digits: &[u64]) -> BigUint {
+    let bytes: Vec<_> = digits.iter().flat_map(|d| d.to_le_bytes()).collect();
+    BigUint::from_bytes_le(&bytes)
+}
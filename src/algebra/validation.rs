@@ -0,0 +1,307 @@
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+use thiserror::Error;
+
+use super::{
+    curve::{Curve, EllipticPoint},
+    fields::zn::BigPrime,
+    primality::baillie_psw,
+    traits::{Field, FinGroup, Group, Sqrt},
+};
+
+/// Embedding degrees up to this bound are rejected, as in SEC 1.
+pub const MOV_DEGREE: u32 = 100;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("field modulus is not prime")]
+    CompositeModulus,
+    #[error("field characteristic is 2 or 3")]
+    SmallCharacteristic,
+    #[error("curve is singular")]
+    Singular,
+    #[error("group order is not prime")]
+    CompositeOrder,
+    #[error("generator is not on curve")]
+    GeneratorNotOnCurve,
+    #[error("group order times generator is not zero")]
+    WrongGeneratorOrder,
+    #[error("cofactor does not match the number of points")]
+    WrongCofactor,
+    #[error("curve is anomalous")]
+    Anomalous,
+    #[error("embedding degree {0} allows the MOV attack")]
+    SmallEmbeddingDegree(u32),
+}
+
+#[derive(Debug, Error)]
+#[error("invalid parameters: {}", .errors.iter().join(", "))]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationError>,
+}
+
+/// Checks that `N::value()` is actually prime.
+pub fn validate_modulus<N: BigPrime>() -> Result<(), ValidationError> {
+    if baillie_psw(&N::value()) {
+        Ok(())
+    } else {
+        Err(ValidationError::CompositeModulus)
+    }
+}
+
+/// Domain parameters as far as [`validate_domain`] needs them, for curves
+/// whose field or coefficients may only be known at runtime.
+pub trait Domain {
+    type Field: Field + Sqrt;
+    type Point: Group;
+
+    /// Coefficients `a` and `b` of `y^2 = x^3 + ax + b`.
+    fn coefficients(&self) -> (Self::Field, Self::Field);
+
+    /// Number of points on the curve divided by the group order.
+    fn cofactor(&self) -> BigUint;
+
+    /// Zero and one of the field.
+    fn constants(&self) -> (Self::Field, Self::Field);
+
+    /// Affine point `(x, y)`, `None` if it is not on the curve.
+    fn point(&self, x: Self::Field, y: Self::Field) -> Option<Self::Point>;
+}
+
+/// Static curve `C` over `F` as a [`Domain`].
+struct Static<F, C>(PhantomData<(F, C)>);
+
+impl<F, C> Domain for Static<F, C>
+where
+    F: Field + Sqrt,
+    C: Curve<F>,
+{
+    type Field = F;
+    type Point = EllipticPoint<F, C>;
+
+    fn coefficients(&self) -> (F, F) {
+        (C::a(), C::b())
+    }
+
+    fn cofactor(&self) -> BigUint {
+        C::cofactor()
+    }
+
+    fn constants(&self) -> (F, F) {
+        (F::zero(), F::one())
+    }
+
+    fn point(&self, x: F, y: F) -> Option<Self::Point> {
+        C::affine(x, y).ok()
+    }
+}
+
+/// Checks domain parameters made of the field `F`, the curve `C` with its
+/// group order and cofactor, and the affine `generator`.
+pub fn validate<F, C>(generator: (F, F)) -> Result<(), ValidationReport>
+where
+    F: Field + FinGroup + Sqrt,
+    C: Curve<F>,
+{
+    let domain = || (Static::<F, C>(PhantomData), generator);
+    validate_domain(&F::order(), &C::group_order(), domain).map(drop)
+}
+
+/// Checks domain parameters over the prime field of order `p` with a
+/// generator of order `n`. The curve and its affine generator are only
+/// built by `domain` once `p` is known to be a prime above 3, and returned
+/// if everything passes.
+///
+/// Checks that make no sense after a failure (e.g. point arithmetic over a
+/// composite modulus) are skipped, everything else is reported at once.
+pub fn validate_domain<D: Domain>(
+    p: &BigUint,
+    n: &BigUint,
+    domain: impl FnOnce() -> (D, (D::Field, D::Field)),
+) -> Result<D, ValidationReport> {
+    let mut errors = vec![];
+    if p <= &BigUint::from(3u32) {
+        errors.push(ValidationError::SmallCharacteristic);
+    } else if !baillie_psw(p) {
+        errors.push(ValidationError::CompositeModulus);
+    }
+    if !baillie_psw(n) {
+        errors.push(ValidationError::CompositeOrder);
+    }
+    if !errors.is_empty() {
+        return Err(ValidationReport { errors });
+    }
+
+    let (domain, generator) = domain();
+    let (a, b) = domain.coefficients();
+    let discriminant = a.clone() * a.clone() * a * BigInt::from(4)
+        + b.clone() * b * BigInt::from(27);
+    if discriminant.is_zero() {
+        errors.push(ValidationError::Singular);
+        return Err(ValidationReport { errors });
+    }
+
+    match domain.point(generator.0, generator.1) {
+        Some(g) => {
+            if !(g * n.clone().into()).is_zero() {
+                errors.push(ValidationError::WrongGeneratorOrder);
+            }
+        }
+        None => errors.push(ValidationError::GeneratorNotOnCurve),
+    }
+
+    // By Hasse's theorem |#E - (p + 1)| <= 2 sqrt(p), and #E kills every point.
+    let points = n * domain.cofactor();
+    let trace = BigInt::from(p + 1u32) - BigInt::from(points.clone());
+    if trace.clone() * trace > BigInt::from(p * 4u32)
+        || !(any_point(&domain) * points.clone().into()).is_zero()
+    {
+        errors.push(ValidationError::WrongCofactor);
+    }
+    if &points == p {
+        errors.push(ValidationError::Anomalous);
+    }
+    let mut power = BigUint::one();
+    for k in 1..=MOV_DEGREE {
+        power = power * p % n;
+        if power.is_one() {
+            errors.push(ValidationError::SmallEmbeddingDegree(k));
+            break;
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(domain)
+    } else {
+        Err(ValidationReport { errors })
+    }
+}
+
+/// First point with a small x coordinate, found without randomness.
+fn any_point<D: Domain>(domain: &D) -> D::Point {
+    let (a, b) = domain.coefficients();
+    let (mut x, one) = domain.constants();
+    loop {
+        let right_side = x.clone() * x.clone() * x.clone()
+            + a.clone() * x.clone()
+            + b.clone();
+        if let Some(y) = right_side.sqrt() {
+            return domain.point(x, y).unwrap();
+        }
+        x = x + one.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::{validate, validate_modulus, ValidationError::*};
+    use crate::algebra::{
+        curve::Curve,
+        fields::zn::{BigPrime, Zn},
+    };
+
+    type F = Zn<P10007>;
+
+    /// Defines a curve over `F` with given coefficients, order and cofactor.
+    macro_rules! curve {
+        ($name:ident, $a:expr, $b:expr, $n:expr, $h:expr) => {
+            struct $name;
+
+            impl Curve<F> for $name {
+                fn group_order() -> BigUint {
+                    BigUint::from($n as usize)
+                }
+
+                fn cofactor() -> BigUint {
+                    BigUint::from($h as usize)
+                }
+
+                fn a() -> F {
+                    F::from($a as usize)
+                }
+
+                fn b() -> F {
+                    F::from($b as usize)
+                }
+            }
+        };
+    }
+
+    curve!(GoodCurve, 1, 28, 9851, 1);
+    curve!(BadOrderCurve, 1, 28, 9839, 1);
+    curve!(BadCofactorCurve, 1, 28, 9851, 2);
+    curve!(AnomalousCurve, 1, 113, 10007, 1);
+    curve!(SupersingularCurve, 1, 0, 139, 72);
+    curve!(SingularCurve, 0, 0, 9851, 1);
+
+    fn errors<C: Curve<F>>(x: usize, y: usize) -> Vec<super::ValidationError> {
+        validate::<F, C>((F::from(x), F::from(y)))
+            .err()
+            .map_or(vec![], |report| report.errors)
+    }
+
+    #[test]
+    fn curves() {
+        assert_eq!(errors::<GoodCurve>(2, 5425), vec![]);
+        assert_eq!(errors::<GoodCurve>(2, 5426), vec![GeneratorNotOnCurve]);
+        assert_eq!(
+            errors::<BadOrderCurve>(2, 5425),
+            vec![WrongGeneratorOrder, WrongCofactor]
+        );
+        assert_eq!(errors::<BadCofactorCurve>(2, 5425), vec![WrongCofactor]);
+        assert_eq!(errors::<AnomalousCurve>(1, 1368), vec![Anomalous]);
+        assert_eq!(
+            errors::<SupersingularCurve>(1154, 8158),
+            vec![SmallEmbeddingDegree(2)]
+        );
+        assert_eq!(errors::<SingularCurve>(0, 0), vec![Singular]);
+    }
+
+    #[test]
+    fn modulus() {
+        assert_eq!(validate_modulus::<P10007>(), Ok(()));
+        assert_eq!(validate_modulus::<Z10011>(), Err(CompositeModulus));
+        assert_eq!(
+            validate::<Zn<Z10011>, Composite>((Zn::from(0), Zn::from(0)))
+                .unwrap_err()
+                .errors,
+            vec![CompositeModulus]
+        );
+    }
+
+    struct Composite;
+
+    impl Curve<Zn<Z10011>> for Composite {
+        fn group_order() -> BigUint {
+            BigUint::from(9851usize)
+        }
+
+        fn a() -> Zn<Z10011> {
+            Zn::from(1)
+        }
+
+        fn b() -> Zn<Z10011> {
+            Zn::from(1)
+        }
+    }
+
+    pub struct P10007;
+    pub struct Z10011;
+
+    impl BigPrime for P10007 {
+        fn value() -> BigUint {
+            BigUint::from(10007usize)
+        }
+    }
+
+    impl BigPrime for Z10011 {
+        fn value() -> BigUint {
+            BigUint::from(10011usize)
+        }
+    }
+}
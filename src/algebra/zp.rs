@@ -1,11 +1,12 @@
 use std::{
     convert::TryInto,
     iter::Sum,
+    marker::PhantomData,
     ops::{Add, Mul, Neg, Sub},
 };
 
 use num_bigint::{BigInt, BigUint};
-use num_traits::{Inv, One, Pow, Zero};
+use num_traits::{One, Pow, Zero};
 use rand::{distributions::Standard, prelude::Distribution};
 use thiserror::Error;
 
@@ -13,7 +14,7 @@ use crate::bytes::{Decoding, Deserialize, Encoding, Serialize};
 
 use super::{
     fields::zn::{BigPrime, Zn},
-    traits::{FinGroup, Group},
+    traits::{ConditionalSwap, Field, FinGroup, Group},
 };
 
 pub trait Generator {
@@ -21,18 +22,32 @@ pub trait Generator {
     fn order() -> BigUint;
 }
 
+/// Safe prime `p = 2q + 1` whose generator spans the quadratic residues, so
+/// `order()` is `q`. Then `p = 3 (mod 4)` and exactly one of `m` and `p - m`
+/// is in the group, which is what [`Encoding`] for [`Zp`] relies on. The top
+/// byte of `p` must be at least 2, so that a block starting with a zero byte
+/// is below `p / 2`.
+pub trait SafePrime: BigPrime + Generator {}
+
+/// Multiplicative group modulo `N`, with residues represented by `F`.
 #[derive(Debug)]
-pub struct Zp<N>(Zn<N>);
+pub struct Zp<N, F = Zn<N>>(F, PhantomData<N>);
+
+impl<N, F> Zp<N, F> {
+    fn new(x: F) -> Self {
+        Self(x, PhantomData)
+    }
+}
 
-impl<N: BigPrime> From<BigUint> for Zp<N> {
+impl<N, F: From<BigUint>> From<BigUint> for Zp<N, F> {
     fn from(x: BigUint) -> Self {
-        Self(Zn::from(x))
+        Self::new(F::from(x))
     }
 }
 
-impl<N: BigPrime> From<BigInt> for Zp<N> {
+impl<N, F: From<BigInt>> From<BigInt> for Zp<N, F> {
     fn from(x: BigInt) -> Self {
-        Self(Zn::from(x))
+        Self::new(F::from(x))
     }
 }
 
@@ -42,46 +57,57 @@ impl<N> From<Zp<N>> for Zn<N> {
     }
 }
 
-impl<N: BigPrime + Generator> Distribution<Zp<N>> for Standard {
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Zp<N> {
+impl<N, F> Distribution<Zp<N, F>> for Standard
+where
+    N: Generator,
+    F: From<BigUint>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Zp<N, F> {
         let degree = rng.gen_range(BigUint::one()..N::order());
         N::generator().pow(degree).into()
     }
 }
 
-impl<N> Clone for Zp<N> {
+impl<N, F: Clone> Clone for Zp<N, F> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self::new(self.0.clone())
     }
 }
 
-impl<N> PartialEq for Zp<N> {
+impl<N, F: PartialEq> PartialEq for Zp<N, F> {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
     }
 }
 
-impl<N> Eq for Zp<N> where Zn<N>: Eq {}
+impl<N, F: Eq> Eq for Zp<N, F> {}
 
-impl<N: BigPrime> Group for Zp<N> {}
+impl<N, F: Field> Group for Zp<N, F> {}
 
-impl<N: BigPrime + Generator> FinGroup for Zp<N> {
+impl<N: Generator, F: Field> FinGroup for Zp<N, F> {
     fn order() -> BigUint {
         N::order()
     }
 }
 
-impl<N: BigPrime> Add for Zp<N> {
+impl<N, F: Field> ConditionalSwap for Zp<N, F> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.0.conditional_swap(&mut other.0, mask);
+    }
+}
+
+impl<N, F: Field> Add for Zp<N, F> {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 * rhs.0)
+        Self::new(self.0 * rhs.0)
     }
 }
 
-impl<N: BigPrime> Zero for Zp<N> {
+impl<N, F: Field> Zero for Zp<N, F> {
     fn zero() -> Self {
-        Self(Zn::one())
+        Self::new(F::one())
     }
 
     fn is_zero(&self) -> bool {
@@ -89,15 +115,15 @@ impl<N: BigPrime> Zero for Zp<N> {
     }
 }
 
-impl<N: BigPrime> Neg for Zp<N> {
+impl<N, F: Field> Neg for Zp<N, F> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        Self(self.0.inv())
+        Self::new(self.0.inv())
     }
 }
 
-impl<N: BigPrime> Sub for Zp<N> {
+impl<N, F: Field> Sub for Zp<N, F> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -105,58 +131,99 @@ impl<N: BigPrime> Sub for Zp<N> {
     }
 }
 
-impl<N: BigPrime> Sum for Zp<N> {
+impl<N, F: Field> Sum for Zp<N, F> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        Self(iter.into_iter().map(|x| x.0).product())
+        Self::new(iter.into_iter().map(|x| x.0).product())
     }
 }
 
-impl<N: BigPrime> Mul<BigInt> for Zp<N> {
+impl<N, F: Field> Mul<BigInt> for Zp<N, F> {
     type Output = Self;
 
     fn mul(self, rhs: BigInt) -> Self::Output {
         match rhs.try_into() {
-            Ok(rhs) => Self(self.0.pow(rhs)),
+            Ok(rhs) => Self::new(self.0.pow(rhs)),
             Err(err) => -self * -err.into_original(),
         }
     }
 }
 
-impl<N: BigPrime + Generator> Encoding for Zp<N> {
+/// Number of message bytes in one element and its byte length.
+fn block_len(modulus: &BigUint) -> (usize, usize) {
+    let len = modulus.bits().div_ceil(8) as usize;
+    (len.saturating_sub(2).min(u8::MAX as usize), len)
+}
+
+/// Packs up to `len - 2` bytes behind a zero byte and their count, then
+/// takes the one of `m` and `p - m` that is a quadratic residue, i.e. in the
+/// group of the generator.
+impl<N, F> Encoding for Zp<N, F>
+where
+    N: SafePrime,
+    F: From<BigUint>,
+{
     fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<Self> {
-        stream.next().map(|x| (N::generator() * BigUint::from(x)).into())
+        let p = N::value();
+        let (chunk_len, len) = block_len(&p);
+        let chunk: Vec<_> = stream.take(chunk_len).collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        let mut bytes = vec![0, chunk.len() as u8];
+        bytes.extend(chunk);
+        bytes.resize(len, 0);
+        let m = BigUint::from_bytes_be(&bytes);
+        let half = (&p - 1u32) >> 1;
+        if m.modpow(&half, &p).is_one() {
+            Some(m.into())
+        } else {
+            Some((p - m).into())
+        }
     }
 }
 
-impl<N: BigPrime + Generator> Decoding for Zp<N> {
-    type Error = TooBig;
+impl<N, F> Decoding for Zp<N, F>
+where
+    N: SafePrime,
+    F: Into<BigUint>,
+{
+    type Error = NotEncoded;
 
     fn decode(self) -> Result<Vec<u8>, Self::Error> {
-        for x in u8::MIN..=u8::MAX {
-            if Self::from(N::generator() * BigUint::from(x)) == self {
-                return Ok(vec![x]);
-            }
+        let p = N::value();
+        let (chunk_len, len) = block_len(&p);
+        let x: BigUint = self.0.into();
+        let m = if x > (&p >> 1) { &p - &x } else { x.clone() };
+        let mut bytes = m.to_bytes_be();
+        if bytes.len() > len - 1 {
+            return Err(NotEncoded(x));
+        }
+        let mut padded = vec![0; len - bytes.len()];
+        padded.append(&mut bytes);
+        let count = padded[1] as usize;
+        if count == 0 || count > chunk_len {
+            return Err(NotEncoded(x));
         }
-        Err(TooBig(self.0.into()))
+        Ok(padded[2..2 + count].to_vec())
     }
 }
 
-impl<N: BigPrime> Serialize for Zp<N> {
+impl<N, F: Serialize> Serialize for Zp<N, F> {
     fn serialize(self) -> Vec<u8> {
         self.0.serialize()
     }
 }
 
-impl<N: BigPrime> Deserialize for Zp<N> {
-    type Error = <Zn<N> as Deserialize>::Error;
+impl<N, F: Deserialize> Deserialize for Zp<N, F> {
+    type Error = F::Error;
 
     fn deserialize(
         stream: &mut impl Iterator<Item = u8>,
     ) -> Result<Option<Self>, Self::Error> {
-        Zn::<N>::deserialize(stream).map(|x| x.map(Self))
+        F::deserialize(stream).map(|x| x.map(Self::new))
     }
 }
 
 #[derive(Debug, Error)]
-#[error("{0} is too big for decoding")]
-pub struct TooBig(BigUint);
+#[error("{0} does not encode a block")]
+pub struct NotEncoded(BigUint);
@@ -2,7 +2,6 @@ use std::time::{Duration, Instant};
 
 use num_bigint::BigUint;
 use num_traits::{One, Pow, Zero};
-use rand::{Rng, thread_rng};
 
 use crate::algebra::{curve::Curve, fields::zn::{BigPrime, Zn}};
 
@@ -76,7 +75,7 @@ fn pollard() {
         let now = Instant::now();
         let z = solve_pollard(&gen, &y);
         let elapsed = now.elapsed();
-        assert!(gen.clone().pow(z.unwrap().into()) == y);
+        assert!(gen.clone().pow(z.unwrap()) == y);
         bench.push(elapsed);
         if (&x % BigUint::from(100_usize)).is_zero() {
             println!("Cracked {} / {}", x, PRIME_ORDER);
@@ -0,0 +1,43 @@
+use std::{hint::black_box, time::Instant};
+
+use num_bigint::{BigUint, RandBigInt};
+use rand::thread_rng;
+
+use crate::{
+    algebra::{
+        fields::zn::{BigPrime, Zn},
+        traits::Field,
+    },
+    ecges::{Z224Mont, N224, Z224},
+};
+
+const ROUNDS: usize = 100;
+const STEPS: usize = 1000;
+
+/// Times chains of multiplications and additions modulo the P-224 prime,
+/// as done by curve arithmetic, with every residue type.
+pub fn fields() {
+    let mut rng = thread_rng();
+    let inputs: Vec<_> = (0..ROUNDS)
+        .map(|_| {
+            let x = rng.gen_biguint_below(&N224::value());
+            (x, rng.gen_biguint_below(&N224::value()))
+        })
+        .collect();
+    time::<Zn<N224>>("Zn", &inputs);
+    time::<Z224Mont>("MontZn", &inputs);
+    time::<Z224>("FixedZn", &inputs);
+}
+
+fn time<F: Field + From<BigUint>>(name: &str, inputs: &[(BigUint, BigUint)]) {
+    let now = Instant::now();
+    for (x, y) in inputs {
+        let (mut x, y) = (F::from(x.clone()), F::from(y.clone()));
+        for _ in 0..STEPS {
+            x = x * y.clone() + y.clone();
+        }
+        black_box(x);
+    }
+    let steps = ROUNDS * STEPS;
+    println!("{}: {} steps in {:?}", name, steps, now.elapsed());
+}
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{convert::Infallible, error::Error};
 
 use thiserror::Error;
 
@@ -57,7 +57,7 @@ pub trait Serialize {
 
 impl<T: Serialize> Serialize for Vec<T> {
     fn serialize(self) -> Vec<u8> {
-        self.into_iter().map(T::serialize).flatten().collect()
+        self.into_iter().flat_map(T::serialize).collect()
     }
 }
 
@@ -110,6 +110,48 @@ impl<T: Deserialize, U: Deserialize> Deserialize for (T, U) {
     }
 }
 
+/// Byte string taking up the rest of a stream, for messages and ciphers of
+/// symmetric schemes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Encoding for Bytes {
+    fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<Self> {
+        let bytes: Vec<_> = stream.collect();
+        if bytes.is_empty() {
+            None
+        } else {
+            Some(Self(bytes))
+        }
+    }
+}
+
+impl Decoding for Bytes {
+    type Error = Infallible;
+
+    fn decode(self) -> Result<Vec<u8>, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Consumes the rest of the stream, so `Bytes` must be the last item of
+/// whatever is deserialized, e.g. the right half of a pair.
+impl Deserialize for Bytes {
+    type Error = Infallible;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        Ok(Self::encode(stream))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Either<L: Error + 'static, R: Error + 'static> {
     #[error(transparent)]
@@ -131,7 +173,7 @@ pub enum ConsumeDeserError<E: Error + 'static> {
 pub fn consume_deser<T: Deserialize>(
     iter: impl IntoIterator<Item = u8>,
 ) -> Result<T, ConsumeDeserError<T::Error>> {
-    let ref mut stream = iter.into_iter();
+    let stream = &mut iter.into_iter();
     let result =
         T::deserialize(stream)?.ok_or(ConsumeDeserError::EmptyStream)?;
     if stream.next().is_some() {
@@ -0,0 +1,226 @@
+//! Brainpool curves from RFC 5639.
+//!
+//! Their primes have no special form, so the fields use the generic [`Zn`]
+//! arithmetic, and `a` is a random constant rather than `-3`.
+
+use num_bigint::BigUint;
+
+use crate::{
+    algebra::{
+        coordinates::Model,
+        curve::{
+            decode_byte, encode_byte, Curve, EllipticPoint, Encoder,
+            NamedCurve, NotFound,
+        },
+        fields::zn::{BigPrime, Zn},
+    },
+    ecges::hex,
+};
+
+macro_rules! brainpool_curve {
+    (
+        $(#[$meta:meta])*
+        $curve:ident, $prime:ident, $field:ident, $point:ident,
+        p = $p:literal,
+        a = $a:literal,
+        b = $b:literal,
+        gx = $gx:literal,
+        gy = $gy:literal,
+        n = $n:literal $(,)?
+    ) => {
+        pub struct $prime;
+
+        impl BigPrime for $prime {
+            fn value() -> BigUint {
+                hex($p)
+            }
+        }
+
+        pub type $field = Zn<$prime>;
+
+        $(#[$meta])*
+        pub struct $curve;
+
+        impl Curve<$field> for $curve {
+            fn group_order() -> BigUint {
+                hex($n)
+            }
+
+            fn a() -> $field {
+                hex($a).into()
+            }
+
+            fn b() -> $field {
+                hex($b).into()
+            }
+
+            fn model() -> Model {
+                Model::Jacobian
+            }
+        }
+
+        impl NamedCurve<$field> for $curve {
+            fn generator() -> $point {
+                $curve::affine(hex($gx).into(), hex($gy).into()).unwrap()
+            }
+        }
+
+        pub type $point = EllipticPoint<$field, $curve>;
+
+        impl Encoder<$point> for $curve {
+            type Error = NotFound;
+
+            fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<$point> {
+                encode_byte(stream)
+            }
+
+            fn decode(item: $point) -> Result<Vec<u8>, Self::Error> {
+                decode_byte(item)
+            }
+        }
+    };
+}
+
+brainpool_curve!(
+    /// brainpoolP256r1 from RFC 5639.
+    BrainpoolP256r1, B256, ZB256, BrainpoolP256r1Point,
+    p = "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+    a = "7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9",
+    b = "26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6",
+    gx = "8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+    gy = "547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+    n = "a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+);
+
+brainpool_curve!(
+    /// brainpoolP384r1 from RFC 5639.
+    BrainpoolP384r1, B384, ZB384, BrainpoolP384r1Point,
+    p = "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b4
+         12b1da197fb71123acd3a729901d1a71874700133107ec53",
+    a = "7bc382c63d8c150c3c72080ace05afa0c2bea28e4fb22787
+         139165efba91f90f8aa5814a503ad4eb04a8c7dd22ce2826",
+    b = "04a8c7dd22ce28268b39b55416f0447c2fb77de107dcd2a6
+         2e880ea53eeb62d57cb4390295dbc9943ab78696fa504c11",
+    gx = "1d1c64f068cf45ffa2a63a81b7c13f6b8847a3e77ef14fe3
+          db7fcafe0cbd10e8e826e03436d646aaef87b2e247d4af1e",
+    gy = "8abe1d7520f9c2a45cb1eb8e95cfd55262b70b29feec5864
+          e19c054ff99129280e4646217791811142820341263c5315",
+    n = "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b3
+         1f166e6cac0425a7cf3ab6af6b7fc3103b883202e9046565",
+);
+
+brainpool_curve!(
+    /// brainpoolP512r1 from RFC 5639.
+    BrainpoolP512r1, B512, ZB512, BrainpoolP512r1Point,
+    p = "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330871
+         7d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3",
+    a = "7830a3318b603b89e2327145ac234cc594cbdd8d3df91610a83441caea9863bc
+         2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94ca",
+    b = "3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a7
+         2bf2c7b9e7c1ac4d77fc94cadc083e67984050b75ebae5dd2809bd638016f723",
+    gx = "81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098e
+          ff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822",
+    gy = "7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111
+          b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892",
+    n = "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870
+         553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069",
+);
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+
+    use super::{BrainpoolP256r1, BrainpoolP384r1, BrainpoolP512r1};
+    use crate::{
+        algebra::{
+            curve::{EllipticPoint, Encoder, NamedCurve},
+            traits::{Field, FinGroup, Sqrt},
+            validation::validate,
+        },
+        bytes::{consume_deser, consume_enc, Decoding, Deserialize, Serialize},
+        ecges::hex,
+    };
+
+    /// Checks the domain parameters and `k * G = (x, y)` for each vector.
+    fn check<F, C>(vectors: &[(&str, &str, &str)])
+    where
+        F: Field + FinGroup + Sqrt + From<BigUint>,
+        C: NamedCurve<F> + Encoder<EllipticPoint<F, C>>,
+        EllipticPoint<F, C>: Serialize + Deserialize,
+    {
+        let generator = C::generator();
+        validate::<F, C>(generator.clone().into()).unwrap();
+        for &(k, x, y) in vectors {
+            let k = BigInt::from(hex(k));
+            let expected = C::affine(hex(x).into(), hex(y).into()).unwrap();
+            assert!(generator.clone() * k.clone() == expected);
+            assert!(generator.clone().mul_secret(&k) == expected);
+            let bytes = expected.clone().serialize();
+            assert!(
+                consume_deser::<EllipticPoint<F, C>>(bytes).unwrap()
+                    == expected
+            );
+        }
+        let minus_one = BigInt::from(C::group_order()) - 1;
+        assert!((generator.clone() * minus_one + generator).is_zero());
+        let point: EllipticPoint<F, C> = consume_enc(vec![7]).ok().unwrap();
+        assert_eq!(point.decode().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn brainpool_p256r1() {
+        check::<_, BrainpoolP256r1>(&[
+            (
+                "2",
+                "743cf1b8b5cd4f2eb55f8aa369593ac436ef044166699e37d51a14c2ce13ea0e",
+                "36ed163337deba9c946fe0bb776529da38df059f69249406892ada097eeb7cd4",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "691024597ea13dc03314771dd794e6d2f50aeb49335d3b03e21b5923c29b9d5c",
+                "3d313de6241323a266524fcbff2658ff2495f46017fcc84f8c9d4c14d8d27917",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn brainpool_p384r1() {
+        check::<_, BrainpoolP384r1>(&[
+            (
+                "2",
+                "2282bc382a2f4dfcb95c3495d7b4fd590ad520b3eb6be4d6
+                 ec2f80c4e0f70df87c4ba74a09b553ebb427b58df9d59fca",
+                "edda83773ac68735768d14a24f37a57ce9bedbc170921ce4
+                 d89dd051728fc3eb4b4ea69ab64fc288f1b29502b6e1d30",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "8615f1af3624b25454cf688b425e3b7d288e3d978ea75f4a
+                 e1f5312155d1ceb1e27bad33e6e90ae66add2814d1df7315",
+                "7dc9028c68569d4163acd9f1911add12207b7e3f95da347f
+                 460ddd629b09966defe1b779940db93405e56fa2e17062e",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn brainpool_p512r1() {
+        check::<_, BrainpoolP512r1>(&[
+            (
+                "2",
+                "9f4945f680edf9800a63285758f399b3d18d8141b8a18064a30d3035f4cb6581
+                 957877f3a8f0f72597116e702915a4f4f698f404089a4cc5080447def02f4850",
+                "6d6b4b188b699c5649826b716292f29d149ce1238d3f1e0f5a2c366b03e5d1b2
+                 fdf99bb1709c700fa5c3b602b0960cbf63a42e4181fd929ce269ad21be592e71",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "7bf9bd50496f269ab8fc0323168e00f6ef173ace42ba4f3883df5cc2d1e1b79b
+                 858d160ca5e24239a2339331e6596e7bd00bad207b6f880950d45ea6c3f32342",
+                "4dfdc5ff3fbf522cb933bc296090238d3e6e9ba1dae771e6ec1699beaeebb54c
+                 937deba16ffe871df435e07329659d6bc7a24657fd2421f97b697a0cd730d51",
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Curve25519 and the X25519 function from RFC 7748.
+
+use num_bigint::BigUint;
+
+use crate::{
+    algebra::{
+        fields::{
+            fixed::{fixed_prime, limbs, FixedZn},
+            zn::BigPrime,
+        },
+        montgomery_curve::MontgomeryCurve,
+    },
+    ecges::hex,
+};
+
+const P: &str =
+    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+
+/// Length of X25519 scalars and coordinates in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// `u` coordinate of the base point.
+pub const BASE_U: [u8; KEY_LEN] = {
+    let mut base = [0; KEY_LEN];
+    base[0] = 9;
+    base
+};
+
+pub struct P25519;
+
+impl BigPrime for P25519 {
+    fn value() -> BigUint {
+        hex(P)
+    }
+}
+
+fixed_prime!(P25519, 4, limbs(P));
+
+pub type Z25519 = FixedZn<P25519, 4>;
+
+/// `y^2 = x^3 + 486662 * x^2 + x`.
+pub struct Curve25519;
+
+impl MontgomeryCurve<Z25519> for Curve25519 {
+    fn a() -> Z25519 {
+        Z25519::from(486662)
+    }
+
+    fn a24() -> Z25519 {
+        Z25519::from(121665)
+    }
+}
+
+/// Clears the three low bits and the top bit and sets bit 254, so that
+/// the scalar is a multiple of the cofactor of fixed length.
+pub fn clamp(mut scalar: [u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    scalar[0] &= 248;
+    scalar[KEY_LEN - 1] &= 127;
+    scalar[KEY_LEN - 1] |= 64;
+    scalar
+}
+
+/// `X25519(k, u)`: both arguments and the result are little-endian. The
+/// top bit of `u` is ignored and non-canonical values are reduced.
+pub fn x25519(scalar: [u8; KEY_LEN], u: [u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    let scalar = BigUint::from_bytes_le(&clamp(scalar));
+    let mut u = u;
+    u[KEY_LEN - 1] &= 127;
+    let u = Z25519::from(BigUint::from_bytes_le(&u));
+    let result = Curve25519::ladder(u, &scalar, 255);
+    let mut bytes = BigUint::from(result).to_bytes_le();
+    bytes.resize(KEY_LEN, 0);
+    let mut result = [0; KEY_LEN];
+    result.copy_from_slice(&bytes);
+    result
+}
+
+/// Public key for the private key `scalar`.
+pub fn x25519_base(scalar: [u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    x25519(scalar, BASE_U)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::{clamp, x25519, x25519_base, Curve25519, BASE_U, KEY_LEN};
+    use crate::algebra::montgomery_curve::MontgomeryCurve;
+
+    fn bytes(string: &str) -> [u8; KEY_LEN] {
+        hex::decode(string).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        let a24 = (Curve25519::a() - 2.into()) / 4.into();
+        assert!(Curve25519::a24() == a24);
+        assert_eq!(clamp([255; KEY_LEN])[0], 248);
+        assert_eq!(clamp([0; KEY_LEN])[KEY_LEN - 1], 64);
+    }
+
+    /// RFC 7748, section 5.2.
+    #[test]
+    fn vectors() {
+        let vectors = [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ];
+        for &(k, u, expected) in &vectors {
+            assert_eq!(x25519(bytes(k), bytes(u)), bytes(expected));
+        }
+    }
+
+    /// RFC 7748, section 5.2, first 1000 iterations.
+    #[test]
+    fn iterated() {
+        let once =
+            "422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079";
+        let thousand =
+            "684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51";
+        let (mut k, mut u) = (BASE_U, BASE_U);
+        for i in 1..=1000 {
+            let result = x25519(k, u);
+            u = k;
+            k = result;
+            if i == 1 {
+                assert_eq!(k, bytes(once));
+            }
+        }
+        assert_eq!(k, bytes(thousand));
+    }
+
+    /// RFC 7748, section 6.1.
+    #[test]
+    fn diffie_hellman() {
+        let keys = [
+            (
+                "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+                "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a",
+            ),
+            (
+                "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+                "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f",
+            ),
+        ];
+        let shared =
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+        for &(private, public) in &keys {
+            assert_eq!(x25519_base(bytes(private)), bytes(public));
+        }
+        let [(alice, alice_public), (bob, bob_public)] = keys;
+        assert_eq!(x25519(bytes(alice), bytes(bob_public)), bytes(shared));
+        assert_eq!(x25519(bytes(bob), bytes(alice_public)), bytes(shared));
+    }
+}
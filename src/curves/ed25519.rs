@@ -0,0 +1,285 @@
+//! Edwards form of Curve25519 and Ed25519 signatures from RFC 8032.
+
+use std::convert::TryInto;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::Zero;
+use thiserror::Error;
+
+use super::curve25519::{clamp, Z25519};
+use crate::{
+    algebra::{
+        edwards::{EdwardsCurve, EdwardsPoint},
+        traits::{multi_scalar_mul, FinGroup},
+    },
+    ecges::hex,
+    hash::sha512::Sha512,
+};
+
+pub const SECRET_LEN: usize = 32;
+pub const PUBLIC_LEN: usize = 32;
+pub const SIGNATURE_LEN: usize = 64;
+
+/// `-x^2 + y^2 = 1 + d * x^2 * y^2` with `d = -121665 / 121666`.
+pub struct Edwards25519;
+
+impl EdwardsCurve<Z25519> for Edwards25519 {
+    fn group_order() -> BigUint {
+        hex("1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed")
+    }
+
+    fn cofactor() -> BigUint {
+        BigUint::from(8u32)
+    }
+
+    fn a() -> Z25519 {
+        -Z25519::from(1)
+    }
+
+    fn d() -> Z25519 {
+        hex("52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3")
+            .into()
+    }
+}
+
+pub type Ed25519Point = EdwardsPoint<Z25519, Edwards25519>;
+
+/// The base point, with `y = 4 / 5` and even `x`.
+pub fn base_point() -> Ed25519Point {
+    Edwards25519::affine(
+        hex("216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a")
+            .into(),
+        hex("6666666666666666666666666666666666666666666666666666666666666658")
+            .into(),
+    )
+    .unwrap()
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Ed25519Error {
+    #[error("invalid point encoding")]
+    InvalidPoint,
+    #[error("signature scalar is not reduced")]
+    NonCanonicalScalar,
+    #[error("signature does not match")]
+    Mismatch,
+}
+
+/// Little-endian `y` with the parity of `x` in the top bit.
+pub fn encode_point(point: Ed25519Point) -> [u8; PUBLIC_LEN] {
+    let (x, y) = point.into();
+    let mut bytes = BigUint::from(y).to_bytes_le();
+    bytes.resize(PUBLIC_LEN, 0);
+    if BigUint::from(x).bit(0) {
+        bytes[PUBLIC_LEN - 1] |= 0x80;
+    }
+    bytes.try_into().unwrap()
+}
+
+/// Inverse of [`encode_point`], rejects non-canonical encodings.
+pub fn decode_point(
+    bytes: &[u8; PUBLIC_LEN],
+) -> Result<Ed25519Point, Ed25519Error> {
+    let mut bytes = *bytes;
+    let sign = bytes[PUBLIC_LEN - 1] >> 7 == 1;
+    bytes[PUBLIC_LEN - 1] &= 0x7f;
+    let y = BigUint::from_bytes_le(&bytes);
+    if y >= Z25519::order() {
+        return Err(Ed25519Error::InvalidPoint);
+    }
+    let y = Z25519::from(y);
+    let x = Edwards25519::solve(y.clone()).ok_or(Ed25519Error::InvalidPoint)?;
+    let x = match BigUint::from(x.clone()) {
+        value if value.is_zero() && sign => {
+            return Err(Ed25519Error::InvalidPoint)
+        }
+        value if value.bit(0) != sign => -x,
+        _ => x,
+    };
+    Ok(Edwards25519::affine(x, y).unwrap())
+}
+
+/// Key pair expanded from a 32-byte secret.
+pub struct SigningKey {
+    scalar: BigInt,
+    prefix: [u8; 32],
+    public: [u8; PUBLIC_LEN],
+}
+
+impl SigningKey {
+    pub fn new(secret: &[u8; SECRET_LEN]) -> Self {
+        let mut hasher = Sha512::new();
+        hasher.update(secret);
+        let digest = hasher.finalize();
+        let scalar = clamp(digest[..32].try_into().unwrap());
+        let scalar = BigInt::from(BigUint::from_bytes_le(&scalar));
+        let public = encode_point(base_point().mul_secret(&scalar));
+        Self {
+            scalar,
+            prefix: digest[32..].try_into().unwrap(),
+            public,
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; PUBLIC_LEN] {
+        self.public
+    }
+
+    /// Deterministic signature `R || S` of `message`.
+    pub fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LEN] {
+        let mut hasher = Sha512::new();
+        hasher.update(&self.prefix).update(message);
+        let nonce = BigInt::from(reduce(hasher.finalize()));
+        let r = encode_point(base_point().mul_secret(&nonce));
+        let k = challenge(&r, &self.public, message);
+        let order = BigInt::from(Edwards25519::group_order());
+        let s = (nonce + BigInt::from(k) * &self.scalar) % order;
+        let mut s = s.magnitude().to_bytes_le();
+        s.resize(32, 0);
+        let mut signature = [0; SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&r);
+        signature[32..].copy_from_slice(&s);
+        signature
+    }
+}
+
+/// Checks the cofactored equation `8 * S * B = 8 * R + 8 * k * A`.
+pub fn verify(
+    public: &[u8; PUBLIC_LEN],
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LEN],
+) -> Result<(), Ed25519Error> {
+    let point = decode_point(public)?;
+    let r_bytes: [u8; 32] = signature[..32].try_into().unwrap();
+    let r = decode_point(&r_bytes)?;
+    let s = BigUint::from_bytes_le(&signature[32..]);
+    if s >= Edwards25519::group_order() {
+        return Err(Ed25519Error::NonCanonicalScalar);
+    }
+    let k = challenge(&r_bytes, public, message);
+    let check = multi_scalar_mul(
+        &[base_point(), r, point],
+        &[s.into(), BigInt::from(-1), -BigInt::from(k)],
+    );
+    let cofactor = BigInt::from(Edwards25519::cofactor());
+    if (check * cofactor).is_zero() {
+        Ok(())
+    } else {
+        Err(Ed25519Error::Mismatch)
+    }
+}
+
+/// `SHA-512(R || A || M)` modulo the group order.
+fn challenge(
+    r: &[u8; 32],
+    public: &[u8; PUBLIC_LEN],
+    message: &[u8],
+) -> BigUint {
+    let mut hasher = Sha512::new();
+    hasher.update(r).update(public).update(message);
+    reduce(hasher.finalize())
+}
+
+fn reduce(digest: [u8; 64]) -> BigUint {
+    BigUint::from_bytes_le(&digest) % Edwards25519::group_order()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+
+    use super::{
+        base_point, decode_point, encode_point, verify, Ed25519Error,
+        Edwards25519, SigningKey,
+    };
+    use crate::algebra::edwards::EdwardsCurve;
+
+    /// RFC 8032, section 7.1, tests 1 to 3.
+    const VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    fn bytes<const N: usize>(string: &str) -> [u8; N] {
+        hex::decode(string).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn base() {
+        let base = base_point();
+        assert!((base.clone() * BigInt::from(Edwards25519::group_order()))
+            .is_zero());
+        let mut encoded = [0x66; 32];
+        encoded[0] = 0x58;
+        assert_eq!(encode_point(base.clone()), encoded);
+        assert!(decode_point(&encoded).unwrap() == base);
+        let point = base * BigInt::from(0xdeadbeefu32);
+        assert!(decode_point(&encode_point(point.clone())).unwrap() == point);
+    }
+
+    #[test]
+    fn vectors() {
+        for &(secret, public, message, signature) in &VECTORS {
+            let key = SigningKey::new(&bytes(secret));
+            let message = hex::decode(message).unwrap();
+            assert_eq!(key.public_key(), bytes(public));
+            assert_eq!(key.sign(&message), bytes(signature));
+            assert_eq!(
+                verify(&bytes(public), &message, &bytes(signature)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        let (secret, public, _, _) = VECTORS[1];
+        let key = SigningKey::new(&bytes(secret));
+        let public = bytes(public);
+        let signature = key.sign(b"message");
+        assert_eq!(
+            verify(&public, b"massage", &signature),
+            Err(Ed25519Error::Mismatch)
+        );
+        let mut forged = signature;
+        let s = BigUint::from_bytes_le(&signature[32..])
+            + Edwards25519::group_order();
+        forged[32..].copy_from_slice(&s.to_bytes_le());
+        assert_eq!(
+            verify(&public, b"message", &forged),
+            Err(Ed25519Error::NonCanonicalScalar)
+        );
+        let mut invalid = [0xff; 32];
+        invalid[31] = 0x7f;
+        assert_eq!(
+            decode_point(&invalid).err(),
+            Some(Ed25519Error::InvalidPoint)
+        );
+        assert_eq!(
+            verify(&invalid, b"message", &signature),
+            Err(Ed25519Error::InvalidPoint)
+        );
+    }
+}
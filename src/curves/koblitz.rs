@@ -0,0 +1,172 @@
+//! Koblitz curves K-163 and K-233 from NIST SP 800-186, defined over
+//! binary fields with `b = 1` and `a` either `0` or `1`.
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::{
+    algebra::{
+        binary_curve::{BinaryCurve, BinaryPoint},
+        fields::binary::{BinaryPoly, Gf2m},
+    },
+    ecges::hex,
+};
+
+/// `x^163 + x^7 + x^6 + x^3 + 1`.
+pub struct F163;
+
+impl BinaryPoly<3> for F163 {
+    const DEGREE: usize = 163;
+    const TERMS: &'static [usize] = &[7, 6, 3, 0];
+}
+
+pub type Gf163 = Gf2m<F163, 3>;
+
+pub struct K163;
+
+pub type K163Point = BinaryPoint<Gf163, K163>;
+
+impl K163 {
+    pub fn generator() -> K163Point {
+        K163::affine(
+            hex("2fe13c0537bbc11acaa07d793de4e6d5e5c94eee8").into(),
+            hex("289070fb05d38ff58321f2e800536d538ccdaa3d9").into(),
+        )
+        .unwrap()
+    }
+}
+
+impl BinaryCurve<Gf163> for K163 {
+    fn group_order() -> BigUint {
+        hex("4000000000000000000020108a2e0cc0d99f8a5ef")
+    }
+
+    fn cofactor() -> BigUint {
+        BigUint::from(2u32)
+    }
+
+    fn a() -> Gf163 {
+        Gf163::one()
+    }
+
+    fn b() -> Gf163 {
+        Gf163::one()
+    }
+}
+
+/// `x^233 + x^74 + 1`.
+pub struct F233;
+
+impl BinaryPoly<4> for F233 {
+    const DEGREE: usize = 233;
+    const TERMS: &'static [usize] = &[74, 0];
+}
+
+pub type Gf233 = Gf2m<F233, 4>;
+
+pub struct K233;
+
+pub type K233Point = BinaryPoint<Gf233, K233>;
+
+impl K233 {
+    pub fn generator() -> K233Point {
+        K233::affine(
+            hex("17232ba853a7e731af129f22ff4149563a419c26bf50a4c9d6eefad6126")
+                .into(),
+            hex("1db537dece819b7f70f555a67c427a8cd9bf18aeb9b56e0c11056fae6a3")
+                .into(),
+        )
+        .unwrap()
+    }
+}
+
+impl BinaryCurve<Gf233> for K233 {
+    fn group_order() -> BigUint {
+        hex("8000000000000000000000000000069d5bb915bcd46efb1ad5f173abdf")
+    }
+
+    fn cofactor() -> BigUint {
+        BigUint::from(4u32)
+    }
+
+    fn a() -> Gf233 {
+        Gf233::zero()
+    }
+
+    fn b() -> Gf233 {
+        Gf233::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+
+    use super::{K163, K233};
+    use crate::{
+        algebra::{
+            binary_curve::{BinaryCurve, BinaryPoint},
+            primality::baillie_psw,
+            traits::{Field, FinGroup},
+        },
+        ecges::hex,
+    };
+
+    /// Checks the order of `generator` and `k * G = (x, y)` for each vector.
+    fn check<F, C>(generator: BinaryPoint<F, C>, vectors: &[(&str, &str, &str)])
+    where
+        F: Field + From<BigUint>,
+        C: BinaryCurve<F>,
+    {
+        let order = C::group_order();
+        assert!(baillie_psw(&order));
+        assert!((generator.clone() * BigInt::from(order.clone())).is_zero());
+        let minus_one = BigInt::from(order) - 1;
+        assert!(generator.clone() * minus_one == -generator.clone());
+        for &(k, x, y) in vectors {
+            let k = BigInt::from(hex(k));
+            let expected = C::affine(hex(x).into(), hex(y).into()).unwrap();
+            assert!(generator.clone() * k.clone() == expected);
+            assert!(generator.clone().mul_secret(&k) == expected);
+        }
+    }
+
+    #[test]
+    fn k163() {
+        check(
+            K163::generator(),
+            &[
+                (
+                    "2",
+                    "cb5ca2738fe300aacfb00b42a77b828d8a5c41eb",
+                    "229c79e9ab85f90acd3d5fa3a696664515efefa6b",
+                ),
+                (
+                    "18ebbb95eed0e13",
+                    "25e375998a309d04e13d0dedccb41c4092e10aa09",
+                    "294931e03634c0372a5fd6ca8b5fc8653f05f3ba9",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn k233() {
+        check(
+            K233::generator(),
+            &[
+                (
+                    "2",
+                    "1a96a52534c02824c92539163f2ed13243feb57b45adbe4cf7ec61957f6",
+                    "1f9d11ccd5ff37c021bb64dff8df25af3ebc5c3f9bfc5cb17b2203703a8",
+                ),
+                (
+                    "18ebbb95eed0e13",
+                    "8db2f6d27ed0b39866937fe5795dcfaa398aad7854865170086aadf6c3",
+                    "129de55eab34ff9e234c702fbf9c9157041b91e8990489bd32067cbdff7",
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod brainpool;
+pub mod curve25519;
+pub mod ed25519;
+pub mod koblitz;
+pub mod nist;
+pub mod secp256k1;
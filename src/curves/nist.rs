@@ -0,0 +1,252 @@
+//! Curves P-256, P-384 and P-521 from NIST SP 800-186.
+//!
+//! All of them have `a = -3` and prime order, so points use Jacobian
+//! coordinates and the cofactor is 1.
+
+use num_bigint::BigUint;
+
+use crate::{
+    algebra::{
+        coordinates::Model,
+        curve::{
+            decode_byte, encode_byte, Curve, EllipticPoint, Encoder,
+            NamedCurve, NotFound,
+        },
+        fields::{
+            fixed::{fixed_prime, limbs, FixedZn},
+            zn::BigPrime,
+        },
+    },
+    ecges::hex,
+};
+
+macro_rules! nist_curve {
+    (
+        $(#[$meta:meta])*
+        $curve:ident, $prime:ident, $field:ident, $point:ident, $limbs:literal,
+        p = $p:literal,
+        n = $n:literal,
+        b = $b:literal,
+        gx = $gx:literal,
+        gy = $gy:literal $(,)?
+    ) => {
+        pub struct $prime;
+
+        impl BigPrime for $prime {
+            fn value() -> BigUint {
+                hex($p)
+            }
+        }
+
+        fixed_prime!($prime, $limbs, limbs($p));
+
+        pub type $field = FixedZn<$prime, $limbs>;
+
+        $(#[$meta])*
+        pub struct $curve;
+
+        impl Curve<$field> for $curve {
+            const A_IS_MINUS_THREE: bool = true;
+
+            fn group_order() -> BigUint {
+                hex($n)
+            }
+
+            fn a() -> $field {
+                -$field::from(3)
+            }
+
+            fn b() -> $field {
+                hex($b).into()
+            }
+
+            fn model() -> Model {
+                Model::Jacobian
+            }
+        }
+
+        impl NamedCurve<$field> for $curve {
+            fn generator() -> $point {
+                $curve::affine(hex($gx).into(), hex($gy).into()).unwrap()
+            }
+        }
+
+        pub type $point = EllipticPoint<$field, $curve>;
+
+        impl Encoder<$point> for $curve {
+            type Error = NotFound;
+
+            fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<$point> {
+                encode_byte(stream)
+            }
+
+            fn decode(item: $point) -> Result<Vec<u8>, Self::Error> {
+                decode_byte(item)
+            }
+        }
+    };
+}
+
+nist_curve!(
+    /// P-256, also known as secp256r1 and prime256v1.
+    P256, N256, Z256, P256Point, 4,
+    p = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    n = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    b = "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    gx = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    gy = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+);
+
+nist_curve!(
+    /// P-384, also known as secp384r1.
+    P384, N384, Z384, P384Point, 6,
+    p = "ffffffffffffffffffffffffffffffffffffffffffffffff
+         fffffffffffffffeffffffff0000000000000000ffffffff",
+    n = "ffffffffffffffffffffffffffffffffffffffffffffffff
+         c7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+    b = "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe814112
+         0314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+    gx = "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b98
+          59f741e082542a385502f25dbf55296c3a545e3872760ab7",
+    gy = "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147c
+          e9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+);
+
+nist_curve!(
+    /// P-521, also known as secp521r1. The field prime is `2^521 - 1`.
+    P521, N521, Z521, P521Point, 9,
+    p = "01ff ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff
+         ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff
+         ffffffff ffffffff",
+    n = "01ff ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff ffffffff
+         fffffffa 51868783 bf2f966b 7fcc0148 f709a5d0 3bb5c9b8 899c47ae
+         bb6fb71e 91386409",
+    b = "0051 953eb961 8e1c9a1f 929a21a0 b68540ee a2da725b 99b315f3 b8b48991
+         8ef109e1 56193951 ec7e937b 1652c0bd 3bb1bf07 3573df88 3d2c34f1
+         ef451fd4 6b503f00",
+    gx = "00c6 858e06b7 0404e9cd 9e3ecb66 2395b442 9c648139 053fb521 f828af60
+          6b4d3dba a14b5e77 efe75928 fe1dc127 a2ffa8de 3348b3c1 856a429b
+          f97e7e31 c2e5bd66",
+    gy = "0118 39296a78 9a3bc004 5c8a5fb4 2c7d1bd9 98f54449 579b4468 17afbd17
+          273e662c 97ee7299 5ef42640 c550b901 3fad0761 353c7086 a272c240
+          88be9476 9fd16650",
+);
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Zero;
+
+    use super::{P256, P384, P521, Z521};
+    use crate::{
+        algebra::{
+            curve::{
+                decode_koblitz, encode_koblitz, koblitz_chunk_len,
+                EllipticPoint, Encoder, NamedCurve,
+            },
+            traits::{Field, FinGroup, Sqrt},
+            validation::validate,
+        },
+        bytes::{consume_deser, consume_enc, Decoding, Deserialize, Serialize},
+        ecges::hex,
+    };
+
+    /// Checks the domain parameters and `k * G = (x, y)` for each vector.
+    fn check<F, C>(vectors: &[(&str, &str, &str)])
+    where
+        F: Field + FinGroup + Sqrt + Serialize + Deserialize + From<BigUint>,
+        BigUint: From<F>,
+        C: NamedCurve<F> + Encoder<EllipticPoint<F, C>>,
+        EllipticPoint<F, C>: Serialize + Deserialize,
+    {
+        let generator = C::generator();
+        validate::<F, C>(generator.clone().into()).unwrap();
+        for &(k, x, y) in vectors {
+            let point = generator.clone() * BigInt::from(hex(k));
+            let expected = C::affine(hex(x).into(), hex(y).into()).unwrap();
+            assert!(point == expected);
+            assert!(generator.clone().mul_secret(&hex(k).into()) == expected);
+            let (px, py) = point.clone().into();
+            assert_eq!(
+                (BigUint::from(px), BigUint::from(py)),
+                (hex(x), hex(y))
+            );
+            let bytes = point.clone().serialize();
+            assert!(
+                consume_deser::<EllipticPoint<F, C>>(bytes).unwrap() == point
+            );
+        }
+        let minus_one = BigInt::from(C::group_order()) - 1;
+        assert!((generator.clone() * minus_one + generator).is_zero());
+        let point: EllipticPoint<F, C> = consume_enc(vec![42]).ok().unwrap();
+        assert_eq!(point.decode().unwrap(), vec![42]);
+    }
+
+    #[test]
+    fn p256() {
+        check::<_, P256>(&[
+            (
+                "2",
+                "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+                "07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "339150844ec15234807fe862a86be77977dbfb3ae3d96f4c22795513aeaab82f",
+                "b1c14ddfdc8ec1b2583f51e85a5eb3a155840f2034730e9b5ada38b674336a21",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn p384() {
+        check::<_, P384>(&[
+            (
+                "2",
+                "08d999057ba3d2d969260045c55b97f089025959a6f434d6
+                 51d207d19fb96e9e4fe0e86ebe0e64f85b96a9c75295df61",
+                "8e80f1fa5b1b3cedb7bfe8dffd6dba74b275d875bc6cc43e
+                 904e505f256ab4255ffd43e94d39e22d61501e700a940e80",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "a499efe48839bc3abcd1c5cedbdd51904f9514db44f4686d
+                 b918983b0c9dc3aee05a88b72433e9515f91a329f5f4fa60",
+                "3b7ca28ef31f809c2f1ba24aaed847d0f8b406a4b8968542
+                 de139db5828ca410e615d1182e25b91b1131e230b727d36a",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn p521() {
+        check::<_, P521>(&[
+            (
+                "2",
+                "00433c219024277e7e682fcb288148c282747403279b1ccc06352c6e5505d769be
+                 97b3b204da6ef55507aa104a3a35c5af41cf2fa364d60fd967f43e3933ba6d783d",
+                "00f4bb8cc7f86db26700a7f3eceeeed3f0b5c6b5107c4da97740ab21a29906c42d
+                 bbb3e377de9f251f6b93937fa99a3248f4eafcbe95edc0f4f71be356d661f41b02",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "01650048fbd63e8c30b305bf36bd7643b91448ef2206e8a0ca84a140789a99b042
+                 3a0a2533ea079ca7e049843e69e5fa2c25a163819110cec1a30acbbb3a422a40d8",
+                "010c9c64a0e0db6052dbc5646687d06dece5e9e0703153efe9cb816fe025e85354
+                 d3c5f869d6db3f4c0c01b5f97919a5e72ceebe03042e5aa99112691cffc2724828",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn koblitz_p521() {
+        assert_eq!(koblitz_chunk_len(&Z521::order()), 63);
+        let text: Vec<u8> = (0..=255).rev().take(100).collect();
+        let mut stream = text.iter().copied();
+        let mut decoded = vec![];
+        while let Some(point) = encode_koblitz::<Z521, P521>(&mut stream) {
+            decoded.extend(decode_koblitz(point).unwrap());
+        }
+        assert_eq!(decoded, text);
+    }
+}
@@ -0,0 +1,202 @@
+//! Curve secp256k1 from SEC 2, `y^2 = x^3 + 7`, used by Bitcoin.
+//!
+//! The field has cube roots of unity, which give the endomorphism
+//! `(x, y) -> (beta * x, y)` used by [`EllipticPoint::mul_glv`].
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::Zero;
+
+use crate::{
+    algebra::{
+        coordinates::Model,
+        curve::{
+            decode_byte, encode_byte, Curve, EllipticPoint, Encoder, GlvCurve,
+            NamedCurve, NotFound,
+        },
+        fields::{
+            fixed::{fixed_prime, limbs, FixedZn},
+            zn::BigPrime,
+        },
+    },
+    ecges::hex,
+};
+
+const P: &str =
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+const N: &str =
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+pub struct N256K1;
+
+impl BigPrime for N256K1 {
+    fn value() -> BigUint {
+        hex(P)
+    }
+}
+
+fixed_prime!(N256K1, 4, limbs(P));
+
+pub type Z256K1 = FixedZn<N256K1, 4>;
+
+pub struct Secp256k1;
+
+pub type Secp256k1Point = EllipticPoint<Z256K1, Secp256k1>;
+
+impl Curve<Z256K1> for Secp256k1 {
+    fn group_order() -> BigUint {
+        hex(N)
+    }
+
+    fn a() -> Z256K1 {
+        Z256K1::zero()
+    }
+
+    fn b() -> Z256K1 {
+        Z256K1::from(7)
+    }
+
+    fn model() -> Model {
+        Model::Jacobian
+    }
+}
+
+impl NamedCurve<Z256K1> for Secp256k1 {
+    fn generator() -> Secp256k1Point {
+        Secp256k1::affine(
+            hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .into(),
+            hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")
+                .into(),
+        )
+        .unwrap()
+    }
+}
+
+impl GlvCurve<Z256K1> for Secp256k1 {
+    fn beta() -> Z256K1 {
+        hex("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee")
+            .into()
+    }
+
+    fn lambda() -> BigUint {
+        hex("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72")
+    }
+
+    fn basis() -> [(BigInt, BigInt); 2] {
+        let a1 = BigInt::from(hex("3086d221a7d46bcde86c90e49284eb15"));
+        let b1 = -BigInt::from(hex("e4437ed6010e88286f547fa90abfe4c3"));
+        let a2 = BigInt::from(hex("114ca50f7a8e2f3f657c1108d9d44cfd8"));
+        [(a1.clone(), b1), (a2, a1)]
+    }
+}
+
+impl Encoder<Secp256k1Point> for Secp256k1 {
+    type Error = NotFound;
+
+    fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<Secp256k1Point> {
+        encode_byte(stream)
+    }
+
+    fn decode(item: Secp256k1Point) -> Result<Vec<u8>, Self::Error> {
+        decode_byte(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, RandBigInt};
+    use rand::thread_rng;
+
+    use super::{Secp256k1, Secp256k1Point, N, Z256K1};
+    use crate::{
+        algebra::{
+            curve::{Curve, GlvCurve, NamedCurve},
+            scalar::glv_decompose,
+            traits::FinGroup,
+            validation::validate,
+        },
+        bytes::{consume_deser, consume_enc, Decoding, Serialize},
+        ecges::hex,
+    };
+
+    fn point(x: &str, y: &str) -> Secp256k1Point {
+        Secp256k1::affine(hex(x).into(), hex(y).into()).unwrap()
+    }
+
+    #[test]
+    fn parameters() {
+        let generator = Secp256k1::generator();
+        validate::<Z256K1, Secp256k1>(generator.into()).unwrap();
+    }
+
+    #[test]
+    fn known_answers() {
+        let generator = Secp256k1::generator();
+        let vectors = [
+            (
+                "2",
+                "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+            ),
+            (
+                "18ebbb95eed0e13",
+                "a90cc3d3f3e146daadfc74ca1372207cb4b725ae708cef713a98edd73d99ef29",
+                "5a79d6b289610c68bc3b47f3d72f9788a26a06868b4d8e433e1e2ad76fb7dc76",
+            ),
+            (
+                "aa5e28d6a97a2479a65527f7290311a3624d4cc0fa1578598ee3c2613bf99522",
+                "34f9460f0e4f08393d192b3c5133a6ba099aa0ad9fd54ebccfacdfa239ff49c6",
+                "0b71ea9bd730fd8923f6d25a7a91e7dd7728a960686cb5a901bb419e0f2ca232",
+            ),
+        ];
+        for &(k, x, y) in &vectors {
+            let k = BigInt::from(hex(k));
+            let expected = point(x, y);
+            assert!(generator.clone() * k.clone() == expected);
+            assert!(generator.clone().mul_glv(&k) == expected);
+            assert!(generator.clone().mul_secret(&k) == expected);
+        }
+        let minus_one = BigInt::from(hex(N)) - 1;
+        assert!(generator.clone() * minus_one == -generator);
+    }
+
+    #[test]
+    fn endomorphism() {
+        let generator = Secp256k1::generator();
+        let lambda = BigInt::from(Secp256k1::lambda());
+        assert!(generator.clone().endomorphism() == generator * lambda);
+    }
+
+    #[test]
+    fn decomposition() {
+        let mut rng = thread_rng();
+        let order = Secp256k1Point::order();
+        let lambda = BigInt::from(Secp256k1::lambda());
+        for _ in 0..100 {
+            let k = rng.gen_bigint(300);
+            let (k1, k2) = glv_decompose(&k, &order, &Secp256k1::basis());
+            assert!(k1.bits() <= 129 && k2.bits() <= 129);
+            let diff = k1 + k2 * &lambda - &k;
+            assert!((diff % BigInt::from(order.clone())) == BigInt::from(0));
+        }
+    }
+
+    #[test]
+    fn mul_glv() {
+        let mut rng = thread_rng();
+        let base = Secp256k1::generator() * BigInt::from(0xdeadbeefu32);
+        for _ in 0..20 {
+            let k = rng.gen_bigint(256);
+            assert!(base.clone().mul_glv(&k) == base.clone() * k);
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let point = Secp256k1::generator() * BigInt::from(12345);
+        let bytes = point.clone().serialize();
+        assert!(consume_deser::<Secp256k1Point>(bytes).unwrap() == point);
+        let point: Secp256k1Point = consume_enc(vec![200]).ok().unwrap();
+        assert_eq!(point.decode().unwrap(), vec![200]);
+    }
+}
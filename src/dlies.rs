@@ -2,9 +2,14 @@ use num_bigint::BigUint;
 
 use crate::{
     algebra::{
-        fields::zn::BigPrime,
-        zp::{Generator, Zp},
+        fields::{
+            fixed::{fixed_prime, limbs, FixedZn},
+            zn::BigPrime,
+        },
+        traits::FinGroup,
+        zp::{Generator, SafePrime, Zp},
     },
+    bytes::{Decoding, Deserialize, Encoding, Serialize},
     ecges::hex,
     encryption::{
         extensions::{make_dyn, public_encryption, PublicEncObject},
@@ -12,56 +17,141 @@ use crate::{
     },
 };
 
-/// From https://www.ipa.go.jp/security/rfc/RFC5114EN.html
-pub struct MODP2048;
+/// Group ffdhe2048 of quadratic residues modulo a safe prime, from
+/// https://www.rfc-editor.org/rfc/rfc7919#appendix-A.1
+pub struct FFDHE2048;
 
-impl BigPrime for MODP2048 {
+impl BigPrime for FFDHE2048 {
     fn value() -> BigUint {
         hex("
-            AD107E1E 9123A9D0 D660FAA7 9559C51F A20D64E5 683B9FD1
-            B54B1597 B61D0A75 E6FA141D F95A56DB AF9A3C40 7BA1DF15
-            EB3D688A 309C180E 1DE6B85A 1274A0A6 6D3F8152 AD6AC212
-            9037C9ED EFDA4DF8 D91E8FEF 55B7394B 7AD5B7D0 B6C12207
-            C9F98D11 ED34DBF6 C6BA0B2C 8BBC27BE 6A00E0A0 B9C49708
-            B3BF8A31 70918836 81286130 BC8985DB 1602E714 415D9330
-            278273C7 DE31EFDC 7310F712 1FD5A074 15987D9A DC0A486D
-            CDF93ACC 44328387 315D75E1 98C641A4 80CD86A1 B9E587E8
-            BE60E69C C928B2B9 C52172E4 13042E9B 23F10B0E 16E79763
-            C9B53DCF 4BA80A29 E3FB73C1 6B8E75B9 7EF363E2 FFA31F71
-            CF9DE538 4E71B81C 0AC4DFFE 0C10E64F
+            FFFFFFFF FFFFFFFF ADF85458 A2BB4A9A AFDC5620 273D3CF1
+            D8B9C583 CE2D3695 A9E13641 146433FB CC939DCE 249B3EF9
+            7D2FE363 630C75D8 F681B202 AEC4617A D3DF1ED5 D5FD6561
+            2433F51F 5F066ED0 85636555 3DED1AF3 B557135E 7F57C935
+            984F0C70 E0E68B77 E2A689DA F3EFE872 1DF158A1 36ADE735
+            30ACCA4F 483A797A BC0AB182 B324FB61 D108A94B B2C8E3FB
+            B96ADAB7 60D7F468 1D4F42A3 DE394DF4 AE56EDE7 6372BB19
+            0B07A7C8 EE0A6D70 9E02FCE1 CDF7E2EC C03404CD 28342F61
+            9172FE9C E98583FF 8E4F1232 EEF28183 C3FE3B1B 4C6FAD73
+            3BB5FCBC 2EC22005 C58EF183 7D1683B2 C6F34A26 C1B2EFFA
+            886B4238 61285C97 FFFFFFFF FFFFFFFF
         ")
     }
 }
 
-impl Generator for MODP2048 {
+impl Generator for FFDHE2048 {
     fn generator() -> BigUint {
-        hex("
-            AC4032EF 4F2D9AE3 9DF30B5C 8FFDAC50 6CDEBE7B 89998CAF
-            74866A08 CFE4FFE3 A6824A4E 10B9A6F0 DD921F01 A70C4AFA
-            AB739D77 00C29F52 C57DB17C 620A8652 BE5E9001 A8D66AD7
-            C1766910 1999024A F4D02727 5AC1348B B8A762D0 521BC98A
-            E2471504 22EA1ED4 09939D54 DA7460CD B5F6C6B2 50717CBE
-            F180EB34 118E98D1 19529A45 D6F83456 6E3025E3 16A330EF
-            BB77A86F 0C1AB15B 051AE3D4 28C8F8AC B70A8137 150B8EEB
-            10E183ED D19963DD D9E263E4 770589EF 6AA21E7F 5F2FF381
-            B539CCE3 409D13CD 566AFBB4 8D6C0191 81E1BCFE 94B30269
-            EDFE72FE 9B6AA4BD 7B5A0F1C 71CFFF4C 19C418E1 F6EC0179
-            81BC087F 2A7065B3 84B890D3 191F2BFA
-        ")
+        BigUint::from(2u32)
     }
 
     fn order() -> BigUint {
-        hex("
-            801C0D34 C58D93FE 99717710 1F80535A 4738CEBC BF389A99
-            B36371EB
-        ")
+        (Self::value() - 1u32) >> 1
     }
 }
 
-pub type Z2048 = Zp<MODP2048>;
+impl SafePrime for FFDHE2048 {}
+
+fixed_prime!(
+    FFDHE2048,
+    32,
+    limbs("
+        FFFFFFFF FFFFFFFF ADF85458 A2BB4A9A AFDC5620 273D3CF1
+        D8B9C583 CE2D3695 A9E13641 146433FB CC939DCE 249B3EF9
+        7D2FE363 630C75D8 F681B202 AEC4617A D3DF1ED5 D5FD6561
+        2433F51F 5F066ED0 85636555 3DED1AF3 B557135E 7F57C935
+        984F0C70 E0E68B77 E2A689DA F3EFE872 1DF158A1 36ADE735
+        30ACCA4F 483A797A BC0AB182 B324FB61 D108A94B B2C8E3FB
+        B96ADAB7 60D7F468 1D4F42A3 DE394DF4 AE56EDE7 6372BB19
+        0B07A7C8 EE0A6D70 9E02FCE1 CDF7E2EC C03404CD 28342F61
+        9172FE9C E98583FF 8E4F1232 EEF28183 C3FE3B1B 4C6FAD73
+        3BB5FCBC 2EC22005 C58EF183 7D1683B2 C6F34A26 C1B2EFFA
+        886B4238 61285C97 FFFFFFFF FFFFFFFF
+    ")
+);
 
-pub fn zp_encryption() -> PublicEncObject {
+pub type Z2048 = Zp<FFDHE2048>;
+
+/// Stack-allocated alternative to [`Z2048`].
+pub type Z2048Fixed = Zp<FFDHE2048, FixedZn<FFDHE2048, 32>>;
+
+/// ElGamal over [`Z2048`] or [`Z2048Fixed`].
+pub fn zp_encryption<T>() -> PublicEncObject
+where
+    T: FinGroup + From<BigUint> + Encoding + Decoding + 'static,
+    T: Serialize + Deserialize,
+{
     make_dyn(public_encryption(el_gamal_const(|| {
-        Z2048::from(MODP2048::generator())
+        T::from(FFDHE2048::generator())
     })))
 }
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::{One, Zero};
+    use rand::thread_rng;
+
+    use super::{zp_encryption, Generator, Z2048Fixed, FFDHE2048, Z2048};
+    use crate::{
+        algebra::{
+            fields::{fixed::FixedZn, zn::BigPrime},
+            primality::baillie_psw,
+            traits::FinGroup,
+        },
+        bytes::{consume_enc, Decoding, Serialize},
+    };
+
+    /// Requirements of [`SafePrime`](crate::algebra::zp::SafePrime).
+    #[test]
+    fn safe_prime() {
+        let (p, q) = (FFDHE2048::value(), FFDHE2048::order());
+        assert!(baillie_psw(&p) && baillie_psw(&q));
+        assert_eq!(&q * 2u32 + 1u32, p);
+        let g = FFDHE2048::generator();
+        assert!(!g.is_one() && g.modpow(&q, &p).is_one());
+        assert!(p.to_bytes_be()[0] >= 2);
+        assert_eq!(FixedZn::<FFDHE2048, 32>::order(), p);
+    }
+
+    #[test]
+    fn fixed_backend() {
+        let fixed = Z2048Fixed::from(FFDHE2048::generator());
+        let plain = Z2048::from(FFDHE2048::generator());
+        let e = BigInt::from(FFDHE2048::order()) - BigInt::from(12345);
+        assert_eq!(
+            (fixed.clone() * e.clone()).serialize(),
+            (plain * e).serialize()
+        );
+        assert!((fixed * FFDHE2048::order().into()).is_zero());
+    }
+
+    #[test]
+    fn blocks() {
+        let text: Vec<u8> = (0..600).map(|i| (i * 7) as u8).collect();
+        let blocks: Vec<Z2048> = consume_enc(text.clone()).ok().unwrap();
+        assert_eq!(blocks.len(), 3);
+        let p = FFDHE2048::value();
+        let half = (&p - 1u32) >> 1;
+        for block in &blocks {
+            let x = BigUint::from_bytes_le(&block.clone().serialize());
+            assert!(x.modpow(&half, &p).is_one());
+        }
+        assert_eq!(blocks.decode().unwrap(), text);
+        assert!(Z2048::zero().decode().is_err());
+
+        let fixed: Vec<Z2048Fixed> = consume_enc(text.clone()).ok().unwrap();
+        assert_eq!(fixed.decode().unwrap(), text);
+    }
+
+    #[test]
+    fn encryption() {
+        let text = "one element carries up to 254 bytes".repeat(10);
+        for scheme in [zp_encryption::<Z2048>(), zp_encryption::<Z2048Fixed>()]
+        {
+            let (enc, dec) = scheme.generate_keys(&mut thread_rng());
+            let cipher = enc.encrypt(&mut thread_rng(), text.clone());
+            assert_eq!(cipher.len(), 2 * 2 * 2 * 256);
+            assert_eq!(dec.decrypt(cipher).unwrap(), text);
+        }
+    }
+}
@@ -1,17 +1,32 @@
+use std::rc::Rc;
+
 use itertools::Itertools;
-use num_bigint::{BigInt, BigUint};
+use num_bigint::BigUint;
 use num_traits::{Num, One};
 use rand::RngCore;
-use thiserror::Error;
 
 use crate::{
     algebra::{
-        curve::{Curve, EllipticPoint, Encoder},
-        fields::zn::{BigPrime, Zn},
+        coordinates::Model,
+        curve::{
+            decode_koblitz, encode_koblitz, Curve, EllipticPoint, Encoder,
+            NamedCurve, NotEmbedded,
+        },
+        dyn_curve::{DynCurve, DynPoint, NotAByte},
+        fields::{
+            fixed::{fixed_prime, limbs, FixedZn},
+            montgomery::{montgomery_prime, MontZn},
+            zn::BigPrime,
+        },
+        sec1::Sec1Error,
     },
+    bytes::Serialize,
     encryption::{
-        extensions::{make_dyn, public_encryption, PublicEncObject},
-        flavours::el_gamal::ElGamal,
+        extensions::{
+            encoded::Codec, encoded_encryption, make_dyn, public_encryption,
+            PublicEncObject,
+        },
+        flavours::{el_gamal::ElGamal, el_gamal_const},
     },
 };
 
@@ -24,13 +39,30 @@ impl BigPrime for N224 {
     }
 }
 
-pub type Z224 = Zn<N224>;
+fixed_prime!(
+    N224,
+    4,
+    limbs("ffffffffffffffffffffffffffffffff000000000000000000000001")
+);
+
+montgomery_prime!(N224);
+
+pub type Z224 = FixedZn<N224, 4>;
+
+/// Heap-allocated alternative to [`Z224`] in Montgomery form.
+pub type Z224Mont = MontZn<N224>;
 
 pub struct P224;
 
+const P224_ORDER: &str =
+    "ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d";
+const P224_B: &str = "b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4";
+
 impl Curve<Z224> for P224 {
+    const A_IS_MINUS_THREE: bool = true;
+
     fn group_order() -> BigUint {
-        hex("ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d")
+        hex(P224_ORDER)
     }
 
     fn a() -> Z224 {
@@ -38,45 +70,113 @@ impl Curve<Z224> for P224 {
     }
 
     fn b() -> Z224 {
-        z224("b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4")
+        hex(P224_B).into()
+    }
+
+    fn model() -> Model {
+        Model::Jacobian
+    }
+}
+
+impl Curve<Z224Mont> for P224 {
+    const A_IS_MINUS_THREE: bool = true;
+
+    fn group_order() -> BigUint {
+        hex(P224_ORDER)
+    }
+
+    fn a() -> Z224Mont {
+        -Z224Mont::from(3)
+    }
+
+    fn b() -> Z224Mont {
+        hex(P224_B).into()
+    }
+
+    fn model() -> Model {
+        Model::Jacobian
     }
 }
 
 pub type Point = EllipticPoint<Z224, P224>;
 
+impl NamedCurve<Z224> for P224 {
+    fn generator() -> Point {
+        P224::affine(
+            z224("b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21"),
+            z224("bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34"),
+        )
+        .unwrap()
+    }
+}
+
+/// Koblitz embedding of 26 bytes per point.
 impl Encoder<Point> for P224 {
-    type Error = NotFound;
+    type Error = NotEmbedded;
 
     fn encode(stream: &mut impl Iterator<Item = u8>) -> Option<Point> {
-        stream.next().map(|x| generator() * BigInt::from(x))
+        encode_koblitz(stream)
     }
 
     fn decode(item: Point) -> Result<Vec<u8>, Self::Error> {
-        for x in u8::MIN..=u8::MAX {
-            if item == generator() * BigInt::from(x) {
-                return Ok(vec![x]);
-            }
-        }
-        Err(NotFound)
+        decode_koblitz(item)
     }
 }
 
-#[derive(Debug, Error)]
-#[error("inverse not found, order is too big")]
-pub struct NotFound;
-
 pub fn ec_encryptor() -> PublicEncObject {
-    make_dyn(public_encryption(ElGamal {
-        get_group_generator: |_: &mut dyn RngCore| generator(),
-    }))
+    make_dyn(public_encryption(ElGamal::from(|_: &mut dyn RngCore| {
+        generator()
+    })))
+}
+
+/// ElGamal over a curve given at runtime, one byte per point.
+pub fn dyn_encryptor(curve: Rc<DynCurve>) -> PublicEncObject {
+    let generator = curve.generator();
+    let el_gamal = el_gamal_const(move || generator.clone());
+    make_dyn(encoded_encryption(curve, el_gamal))
+}
+
+/// Every byte is the point [`DynCurve::encode_byte`], ciphers are pairs of
+/// SEC1 points.
+impl Codec for Rc<DynCurve> {
+    type Message = DynPoint;
+    type Cipher = (DynPoint, DynPoint);
+    type DecodingError = NotAByte;
+    type DeserializationError = Sec1Error;
+
+    fn encode(
+        &self,
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Option<DynPoint> {
+        stream.next().map(|x| self.encode_byte(x))
+    }
+
+    fn decode(&self, message: DynPoint) -> Result<Vec<u8>, NotAByte> {
+        Ok(vec![self.decode_byte(&message)?])
+    }
+
+    fn serialize(&self, cipher: (DynPoint, DynPoint)) -> Vec<u8> {
+        cipher.serialize()
+    }
+
+    fn deserialize(
+        &self,
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<(DynPoint, DynPoint)>, Sec1Error> {
+        match self.read_point(stream)? {
+            Some(salt) => {
+                let point = self
+                    .read_point(stream)?
+                    .ok_or(Sec1Error::NotEnoughBytes)?;
+                Ok(Some((salt, point)))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 pub fn generator() -> Point {
-    P224::affine(
-        z224("b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21"),
-        z224("bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34"),
-    )
-    .unwrap()
+    P224::generator()
 }
 
 pub fn hex(string: &str) -> BigUint {
@@ -87,3 +187,189 @@ pub fn hex(string: &str) -> BigUint {
 fn z224(string: &str) -> Z224 {
     hex(string).into()
 }
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint, RandBigInt};
+    use num_traits::{One, Zero};
+    use rand::{thread_rng, Rng};
+
+    use super::{
+        dyn_encryptor, ec_encryptor, generator, hex, Point, Z224Mont, N224,
+        P224, P224_B, P224_ORDER, Z224,
+    };
+    use crate::{
+        algebra::{
+            curve::{Curve, EllipticPoint, Encoder, Legacy},
+            dyn_curve::DynCurve,
+            fields::zn::BigPrime,
+            primality::baillie_psw,
+            traits::{multi_scalar_mul, FinGroup},
+            validation::validate,
+        },
+        bytes::{consume_deser, consume_enc, Decoding, Serialize},
+    };
+
+    #[test]
+    fn primes() {
+        assert!(baillie_psw(&N224::value()));
+        assert!(baillie_psw(&Point::order()));
+    }
+
+    #[test]
+    fn parameters() {
+        let (x, y) = generator().into();
+        validate::<Z224, P224>((x.clone(), y.clone())).unwrap();
+        let (x, y) = (BigUint::from(x), BigUint::from(y));
+        validate::<Z224Mont, P224>((x.into(), y.into())).unwrap();
+    }
+
+    #[test]
+    fn generator_order() {
+        assert!((generator() * Point::order().into()).is_zero());
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let (x, y) = rng.gen::<Point>().into();
+            let root = P224::solve(x.clone()).unwrap();
+            assert!(root == y || root == -y.clone());
+            assert!(P224::affine(x, y).is_ok());
+        }
+    }
+
+    #[test]
+    fn mul_secret() {
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let k = rng.gen_bigint_range(
+                &-BigInt::from(Point::order()),
+                &BigInt::from(Point::order()),
+            );
+            let point = generator() * BigInt::from(rng.gen::<u32>());
+            assert!(point.clone().mul_secret(&k) == point * k);
+        }
+    }
+
+    #[test]
+    fn precomputed() {
+        let mut rng = thread_rng();
+        let table = generator().precompute(4);
+        for _ in 0..20 {
+            let k = rng.gen_bigint(300);
+            let expected = generator() * k.clone();
+            assert!(table.mul(&k) == expected);
+            assert!(generator().mul_wnaf(&k, 5) == expected);
+        }
+    }
+
+    #[test]
+    fn multi_scalar() {
+        let mut rng = thread_rng();
+        for &n in &[3, 40] {
+            let points: Vec<_> = (1..=n)
+                .map(|i| generator() * BigInt::from(i * 1009))
+                .collect();
+            let scalars: Vec<_> = (0..n).map(|_| rng.gen_bigint(224)).collect();
+            let expected: Point = points
+                .iter()
+                .zip(&scalars)
+                .map(|(p, k)| p.clone() * k.clone())
+                .sum();
+            assert!(multi_scalar_mul(&points, &scalars) == expected);
+        }
+    }
+
+    #[test]
+    fn dyn_round_trip() {
+        let (x, y) = generator().into();
+        let curve = DynCurve::new(
+            N224::value(),
+            (N224::value() - 3u32, hex(P224_B)),
+            (x.into(), y.into()),
+            hex(P224_ORDER),
+            BigUint::one(),
+        )
+        .unwrap();
+        let (enc, dec) =
+            dyn_encryptor(curve.into()).generate_keys(&mut thread_rng());
+        let cipher = enc.encrypt(&mut thread_rng(), "hi".to_string());
+        assert_eq!(dec.decrypt(cipher).unwrap(), "hi");
+    }
+
+    #[test]
+    fn sec1() {
+        let (x, y) = generator().into();
+        let (x, y) = (BigUint::from(x), BigUint::from(y));
+        let mut expected = vec![4];
+        expected.append(&mut x.to_bytes_be());
+        expected.append(&mut y.to_bytes_be());
+        assert_eq!(generator().serialize(), expected);
+        assert_eq!(
+            generator().to_sec1(true),
+            [&[2], &expected[1..29]].concat()
+        );
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let point = generator() * rng.gen_bigint(224);
+            for &compressed in &[false, true] {
+                let bytes = point.clone().to_sec1(compressed);
+                assert!(consume_deser::<Point>(bytes).unwrap() == point);
+            }
+        }
+        assert_eq!(Point::zero().serialize(), vec![0]);
+        assert!(consume_deser::<Point>(vec![0]).unwrap().is_zero());
+        let mut off_curve = expected;
+        off_curve[56] ^= 1;
+        assert!(consume_deser::<Point>(off_curve).is_err());
+    }
+
+    #[test]
+    fn legacy() {
+        let (x, y) = generator().into();
+        let mut expected = vec![1];
+        expected.append(&mut x.serialize());
+        expected.append(&mut y.serialize());
+        assert_eq!(Legacy(generator()).serialize(), expected);
+        let point = consume_deser::<Legacy<Point>>(expected).unwrap().0;
+        assert!(point == generator());
+        assert_eq!(Legacy(Point::zero()).serialize(), vec![0]);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let (x, y) = generator().into();
+        let (x, y) = (BigUint::from(x), BigUint::from(y));
+        let mont: EllipticPoint<Z224Mont, P224> =
+            P224::affine(x.into(), y.into()).unwrap();
+        let k = BigInt::from(0xdeadbeefu32);
+        assert_eq!(
+            (mont * k.clone()).serialize(),
+            (generator() * k).serialize()
+        );
+    }
+
+    #[test]
+    fn koblitz() {
+        let text: Vec<u8> = (0..60).collect();
+        let points: Vec<Point> = consume_enc(text.clone()).ok().unwrap();
+        assert_eq!(points.len(), 3);
+        for (point, chunk) in points.iter().zip(text.chunks(26)) {
+            let (x, y) = point.clone().into();
+            assert!(P224::affine(x.clone(), y).is_ok());
+            let x = BigUint::from(x).to_bytes_be();
+            assert_eq!(x[0] as usize, chunk.len());
+            assert_eq!(&x[1..=chunk.len()], chunk);
+        }
+        assert_eq!(points.decode().unwrap(), text);
+        assert!(P224::decode(generator()).is_err());
+        assert!(P224::decode(Point::zero()).is_err());
+
+        let (enc, dec) = ec_encryptor().generate_keys(&mut thread_rng());
+        let text = "Koblitz embedding packs many bytes per point".repeat(4);
+        let cipher = enc.encrypt(&mut thread_rng(), text.clone());
+        assert_eq!(dec.decrypt(cipher).unwrap(), text);
+    }
+}
@@ -0,0 +1,128 @@
+use std::error::Error;
+
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::{bytes::Bytes, encryption::base::encryption::*};
+
+/// Conversion of messages and ciphers to bytes which needs parameters only
+/// known at runtime, like [`Encoding`](crate::bytes::Encoding) and
+/// [`Serialize`](crate::bytes::Serialize) with a receiver.
+pub trait Codec {
+    type Message;
+    type Cipher;
+    type DecodingError: Error + 'static;
+    type DeserializationError: Error + 'static;
+
+    /// Next message of the stream, `None` once it is empty.
+    fn encode(
+        &self,
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Option<Self::Message>;
+
+    fn decode(
+        &self,
+        message: Self::Message,
+    ) -> Result<Vec<u8>, Self::DecodingError>;
+
+    fn serialize(&self, cipher: Self::Cipher) -> Vec<u8>;
+
+    /// Next cipher of the stream, `None` once it is empty.
+    fn deserialize(
+        &self,
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self::Cipher>, Self::DeserializationError>;
+}
+
+/// Scheme `X` over byte strings, which are split into as many messages as
+/// the codec `K` needs. Keys carry the codec, so they can read ciphers on
+/// their own.
+pub struct Encoded<K, X>(pub K, pub X);
+
+impl<K, X> Enc for Encoded<K, X> {
+    type Message = Bytes;
+    type Cipher = Bytes;
+}
+
+impl<K, X> PublicKeyEncryption for Encoded<K, X>
+where
+    K: Codec + Clone + 'static,
+    X: PublicKeyEncryption<Message = K::Message, Cipher = K::Cipher>,
+{
+    type PublicKey = Encoded<K, X::PublicKey>;
+    type Secret = Encoded<K, X::Secret>;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::PublicKey, Self::Secret) {
+        let (enc, dec) = self.1.generate_keys(rng);
+        (Encoded(self.0.clone(), enc), Encoded(self.0.clone(), dec))
+    }
+}
+
+impl<K, X> Encryptor for Encoded<K, X>
+where
+    K: Codec,
+    X: Encryptor<Message = K::Message, Cipher = K::Cipher>,
+{
+    fn encrypt(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Cipher {
+        let Encoded(codec, encryptor) = self;
+        let mut stream = message.0.into_iter();
+        let mut cipher = vec![];
+        while let Some(message) = codec.encode(&mut stream) {
+            let mut item = codec.serialize(encryptor.encrypt(rng, message));
+            cipher.append(&mut item);
+        }
+        Bytes(cipher)
+    }
+}
+
+impl<K, X> Decryptor for Encoded<K, X>
+where
+    K: Codec,
+    X: Decryptor<Message = K::Message, Cipher = K::Cipher>,
+{
+    type Error = CodecDecryptionError<
+        K::DeserializationError,
+        X::Error,
+        K::DecodingError,
+    >;
+
+    fn decrypt(
+        &self,
+        cipher: Self::Cipher,
+    ) -> Result<Self::Message, Self::Error> {
+        let Encoded(codec, decryptor) = self;
+        let mut stream = cipher.0.into_iter();
+        let mut message = vec![];
+        while let Some(cipher) = codec
+            .deserialize(&mut stream)
+            .map_err(Self::Error::Deserialization)?
+        {
+            let item =
+                decryptor.decrypt(cipher).map_err(Self::Error::Decryption)?;
+            let bytes = codec.decode(item).map_err(Self::Error::Decoding)?;
+            message.extend(bytes);
+        }
+        Ok(Bytes(message))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CodecDecryptionError<
+    S: Error + 'static,
+    R: Error + 'static,
+    C: Error + 'static,
+> {
+    #[error("Deserialization error")]
+    Deserialization(#[source] S),
+    #[error("Decryption error")]
+    Decryption(#[source] R),
+    #[error("Decoding error")]
+    Decoding(#[source] C),
+}
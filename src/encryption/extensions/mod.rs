@@ -1,6 +1,7 @@
 use crate::bytes::{Decoding, Deserialize, Encoding, Serialize};
 
 use self::{
+    encoded::{Codec, Encoded},
     hybrid::Hybrid,
     object::{DynEncryption, DynError},
     stringer::Stringer,
@@ -12,6 +13,7 @@ use super::base::{
     encryption::{Decryptor, Encryptor, PrivateKey, PublicKeyEncryption},
 };
 
+pub mod encoded;
 pub mod hybrid;
 pub mod object;
 pub mod stringer;
@@ -43,6 +45,19 @@ where
     Stringer(Vectorized(encryption))
 }
 
+/// Like [`public_encryption`] for messages and ciphers which need the
+/// runtime parameters of `codec` to be read and written.
+pub fn encoded_encryption<K, E>(
+    codec: K,
+    encryption: E,
+) -> impl PublicKeyEncryption<Message = String, Cipher = String>
+where
+    K: Codec + Clone + 'static,
+    E: PublicKeyEncryption<Message = K::Message, Cipher = K::Cipher> + 'static,
+{
+    Stringer(Encoded(codec, encryption))
+}
+
 pub fn hybrid_encryption<E, K>(
     encapsulation: E,
 ) -> impl PublicKeyEncryption<Message = String, Cipher = String>
@@ -44,7 +44,7 @@ impl<X: Encryptor> Encryptor for DynEncryption<X> {
 
 #[derive(Debug, Error)]
 #[error(transparent)]
-pub struct DynError(Box<dyn Error>);
+pub struct DynError(pub(crate) Box<dyn Error>);
 
 impl<X: Decryptor> Decryptor for DynEncryption<X> {
     type Error = DynError;
@@ -0,0 +1,199 @@
+//! ChaCha20 stream cipher from RFC 8439, section 2.4.
+
+use std::convert::TryInto;
+
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::{bytes::Bytes, encryption::base::encryption::*};
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+
+const BLOCK_LEN: usize = 64;
+
+/// "expand 32-byte k" as little-endian words.
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+fn quarter_round(
+    state: &mut [u32; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// Keystream block number `counter`.
+pub fn block(
+    key: &[u8; KEY_LEN],
+    counter: u32,
+    nonce: &[u8; NONCE_LEN],
+) -> [u8; BLOCK_LEN] {
+    let words = |bytes: &[u8]| {
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>()
+    };
+    let mut initial = [0; 16];
+    initial[..4].copy_from_slice(&CONSTANTS);
+    initial[4..12].copy_from_slice(&words(key));
+    initial[12] = counter;
+    initial[13..].copy_from_slice(&words(nonce));
+    let mut state = initial;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    let mut result = [0; BLOCK_LEN];
+    for (i, chunk) in result.chunks_exact_mut(4).enumerate() {
+        let word = state[i].wrapping_add(initial[i]);
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    result
+}
+
+/// XORs `data` with the keystream starting at block `counter`.
+pub fn apply_keystream(
+    key: &[u8; KEY_LEN],
+    counter: u32,
+    nonce: &[u8; NONCE_LEN],
+    data: &mut [u8],
+) {
+    for (i, chunk) in data.chunks_mut(BLOCK_LEN).enumerate() {
+        let keystream = block(key, counter.wrapping_add(i as u32), nonce);
+        for (byte, key) in chunk.iter_mut().zip(keystream.iter()) {
+            *byte ^= key;
+        }
+    }
+}
+
+/// Unauthenticated ChaCha20 with random keys.
+pub struct ChaCha20;
+
+/// ChaCha20 key encrypting under a random nonce, which is put in front of the
+/// cipher text.
+#[derive(Clone)]
+pub struct ChaCha20Key(pub [u8; KEY_LEN]);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Cipher is shorter than a nonce")]
+pub struct NoNonce;
+
+impl PrivateKeyEncryption for ChaCha20 {
+    type Secret = ChaCha20Key;
+
+    fn generate_key(&self, rng: &mut dyn RngCore) -> Self::Secret {
+        let mut key = [0; KEY_LEN];
+        rng.fill_bytes(&mut key);
+        ChaCha20Key(key)
+    }
+}
+
+impl Enc for ChaCha20Key {
+    type Message = Bytes;
+    type Cipher = Bytes;
+}
+
+impl Encryptor for ChaCha20Key {
+    fn encrypt(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Cipher {
+        let mut nonce = [0; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        let mut data = message.0;
+        apply_keystream(&self.0, 0, &nonce, &mut data);
+        Bytes([&nonce[..], &data].concat())
+    }
+}
+
+impl Decryptor for ChaCha20Key {
+    type Error = NoNonce;
+
+    fn decrypt(
+        &self,
+        cipher: Self::Cipher,
+    ) -> Result<Self::Message, Self::Error> {
+        if cipher.0.len() < NONCE_LEN {
+            return Err(NoNonce);
+        }
+        let (nonce, data) = cipher.0.split_at(NONCE_LEN);
+        let mut data = data.to_vec();
+        apply_keystream(&self.0, 0, nonce.try_into().unwrap(), &mut data);
+        Ok(Bytes(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use rand::thread_rng;
+
+    use super::{apply_keystream, ChaCha20, NoNonce};
+    use crate::{
+        bytes::Bytes,
+        encryption::{
+            base::encryption::{Decryptor, Encryptor, PrivateKeyEncryption},
+            extensions::stringer::Stringer,
+        },
+    };
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+        If I could offer you only one tip for the future, sunscreen would be \
+        it.";
+
+    /// Section 2.4.2 of RFC 8439.
+    #[test]
+    fn rfc8439() {
+        let key: Vec<u8> = (0..32).collect();
+        let nonce = hex::decode("000000000000004a00000000").unwrap();
+        let mut data = SUNSCREEN.to_vec();
+        apply_keystream(
+            key.as_slice().try_into().unwrap(),
+            1,
+            nonce.as_slice().try_into().unwrap(),
+            &mut data,
+        );
+        assert_eq!(
+            hex::encode(&data),
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b\
+             f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8\
+             07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736\
+             5af90bbf74a35be6b40b8eedf2785e42874d"
+        );
+    }
+
+    #[test]
+    fn scheme() {
+        let mut rng = thread_rng();
+        let key = ChaCha20.generate_key(&mut rng);
+        let message = Bytes(vec![7; 200]);
+        let cipher = key.encrypt(&mut rng, message.clone());
+        assert_eq!(cipher.0.len(), 12 + 200);
+        assert_ne!(cipher, key.encrypt(&mut rng, message.clone()));
+        assert_eq!(key.decrypt(cipher), Ok(message));
+        assert_eq!(key.decrypt(Bytes(vec![0; 11])), Err(NoNonce));
+
+        let key = Stringer(ChaCha20).generate_key(&mut rng);
+        let cipher = key.encrypt(&mut rng, "stream".to_string());
+        assert_eq!(key.decrypt(cipher).unwrap(), "stream");
+    }
+}
@@ -0,0 +1,197 @@
+//! ChaCha20-Poly1305 AEAD from RFC 8439, section 2.8.
+
+use std::convert::TryInto;
+
+use rand::RngCore;
+use thiserror::Error;
+
+use super::{
+    chacha20::{apply_keystream, block, KEY_LEN, NONCE_LEN},
+    poly1305::{poly1305, TAG_LEN},
+};
+use crate::{bytes::Bytes, encryption::base::encryption::*};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AeadError {
+    #[error("Cipher is shorter than a nonce and a tag")]
+    TooShort,
+    #[error("Authentication tag does not match")]
+    TagMismatch,
+}
+
+fn tag(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    cipher: &[u8],
+) -> [u8; TAG_LEN] {
+    let poly_key = block(key, 0, nonce)[..32].try_into().unwrap();
+    let pad = |data: &[u8]| vec![0; (16 - data.len() % 16) % 16];
+    let lengths = [aad.len() as u64, cipher.len() as u64];
+    let data = [
+        aad,
+        &pad(aad),
+        cipher,
+        &pad(cipher),
+        &lengths[0].to_le_bytes(),
+        &lengths[1].to_le_bytes(),
+    ]
+    .concat();
+    poly1305(&poly_key, &data)
+}
+
+/// Cipher text followed by the tag.
+pub fn seal(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Vec<u8> {
+    let mut result = plaintext.to_vec();
+    apply_keystream(key, 1, nonce, &mut result);
+    let tag = tag(key, nonce, aad, &result);
+    result.extend_from_slice(&tag);
+    result
+}
+
+pub fn open(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    sealed: &[u8],
+) -> Result<Vec<u8>, AeadError> {
+    if sealed.len() < TAG_LEN {
+        return Err(AeadError::TooShort);
+    }
+    let (cipher, expected) = sealed.split_at(sealed.len() - TAG_LEN);
+    let actual = tag(key, nonce, aad, cipher);
+    let diff = actual
+        .iter()
+        .zip(expected)
+        .fold(0, |acc, (a, b)| acc | a ^ b);
+    if diff != 0 {
+        return Err(AeadError::TagMismatch);
+    }
+    let mut result = cipher.to_vec();
+    apply_keystream(key, 1, nonce, &mut result);
+    Ok(result)
+}
+
+/// ChaCha20-Poly1305 with random keys.
+pub struct ChaCha20Poly1305;
+
+impl PrivateKeyEncryption for ChaCha20Poly1305 {
+    type Secret = ChaCha20Poly1305Key;
+
+    fn generate_key(&self, rng: &mut dyn RngCore) -> Self::Secret {
+        let mut key = [0; KEY_LEN];
+        rng.fill_bytes(&mut key);
+        ChaCha20Poly1305Key(key)
+    }
+}
+
+/// ChaCha20-Poly1305 key encrypting under a random nonce, which is put in
+/// front of the sealed message.
+#[derive(Clone)]
+pub struct ChaCha20Poly1305Key(pub [u8; KEY_LEN]);
+
+impl Enc for ChaCha20Poly1305Key {
+    type Message = Bytes;
+    type Cipher = Bytes;
+}
+
+impl Encryptor for ChaCha20Poly1305Key {
+    fn encrypt(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Cipher {
+        let mut nonce = [0; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        let mut sealed = seal(&self.0, &nonce, &[], &message.0);
+        let mut result = nonce.to_vec();
+        result.append(&mut sealed);
+        Bytes(result)
+    }
+}
+
+impl Decryptor for ChaCha20Poly1305Key {
+    type Error = AeadError;
+
+    fn decrypt(
+        &self,
+        cipher: Self::Cipher,
+    ) -> Result<Self::Message, Self::Error> {
+        if cipher.0.len() < NONCE_LEN + TAG_LEN {
+            return Err(AeadError::TooShort);
+        }
+        let (nonce, sealed) = cipher.0.split_at(NONCE_LEN);
+        open(&self.0, nonce.try_into().unwrap(), &[], sealed).map(Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use rand::thread_rng;
+
+    use super::{open, seal, AeadError, ChaCha20Poly1305};
+    use crate::{
+        bytes::Bytes,
+        encryption::{
+            base::encryption::{Decryptor, Encryptor, PrivateKeyEncryption},
+            extensions::{stringer::Stringer, vectorized::Vectorized},
+        },
+    };
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+        If I could offer you only one tip for the future, sunscreen would be \
+        it.";
+
+    /// Section 2.8.2 of RFC 8439.
+    #[test]
+    fn rfc8439() {
+        let key: Vec<u8> = (0x80..0xa0).collect();
+        let key = key.as_slice().try_into().unwrap();
+        let nonce = hex::decode("070000004041424344454647").unwrap();
+        let nonce = nonce.as_slice().try_into().unwrap();
+        let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let sealed = seal(key, nonce, &aad, SUNSCREEN);
+        assert_eq!(
+            hex::encode(&sealed),
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+             3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+             92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+             3ff4def08e4b7a9de576d26586cec64b6116\
+             1ae10b594f09e26a7e902ecbd0600691"
+        );
+        assert_eq!(open(key, nonce, &aad, &sealed).unwrap(), SUNSCREEN);
+        assert_eq!(open(key, nonce, &[], &sealed), Err(AeadError::TagMismatch));
+    }
+
+    #[test]
+    fn key() {
+        let mut rng = thread_rng();
+        let key = ChaCha20Poly1305.generate_key(&mut rng);
+        let message = Bytes(b"attack at dawn".to_vec());
+        let cipher = key.encrypt(&mut rng, message.clone());
+        assert_eq!(cipher.0.len(), 12 + 14 + 16);
+        assert_eq!(key.decrypt(cipher.clone()).unwrap(), message);
+        let mut forged = cipher;
+        forged.0[20] ^= 1;
+        assert_eq!(key.decrypt(forged), Err(AeadError::TagMismatch));
+        assert_eq!(key.decrypt(Bytes(vec![0; 27])), Err(AeadError::TooShort));
+    }
+
+    #[test]
+    fn strings() {
+        let mut rng = thread_rng();
+        let key = Stringer(Vectorized(ChaCha20Poly1305)).generate_key(&mut rng);
+        let cipher = key.encrypt(&mut rng, "sealed".to_string());
+        assert_eq!(key.decrypt(cipher.clone()).unwrap(), "sealed");
+        let other =
+            Stringer(Vectorized(ChaCha20Poly1305)).generate_key(&mut rng);
+        assert!(other.decrypt(cipher).is_err());
+    }
+}
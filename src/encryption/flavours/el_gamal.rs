@@ -4,7 +4,13 @@ use num_bigint::BigInt;
 use num_traits::One;
 use rand::{Rng, RngCore};
 
-use crate::{algebra::traits::FinGroup, encryption::base::encryption::*};
+use crate::{
+    algebra::{
+        scalar::{FixedBaseTable, DEFAULT_WINDOW},
+        traits::DynFinGroup,
+    },
+    encryption::base::encryption::*,
+};
 
 pub struct ElGamal<F> {
     pub get_group_generator: F,
@@ -21,6 +27,24 @@ impl<F> From<F> for ElGamal<F> {
 pub struct ElGamalPublicKey<T> {
     pub group_generator: T,
     pub key: T,
+    /// Tables of `group_generator` and `key` used by [`Encryptor::encrypt`]
+    /// while their bases still match these fields.
+    pub tables: Option<(FixedBaseTable<T>, FixedBaseTable<T>)>,
+}
+
+impl<T: DynFinGroup> ElGamalPublicKey<T> {
+    /// Key with both tables precomputed.
+    pub fn new(group_generator: T, key: T) -> Self {
+        let tables = Some((
+            FixedBaseTable::new(group_generator.clone(), DEFAULT_WINDOW),
+            FixedBaseTable::new(key.clone(), DEFAULT_WINDOW),
+        ));
+        Self {
+            group_generator,
+            key,
+            tables,
+        }
+    }
 }
 
 pub struct ElGamalSecret<T> {
@@ -40,7 +64,7 @@ where
 impl<F, T> PublicKeyEncryption for ElGamal<F>
 where
     F: Fn(&mut dyn RngCore) -> T,
-    T: FinGroup + 'static,
+    T: DynFinGroup + 'static,
 {
     type PublicKey = ElGamalPublicKey<T>;
     type Secret = ElGamalSecret<T>;
@@ -50,14 +74,11 @@ where
         rng: &mut dyn RngCore,
     ) -> (Self::PublicKey, Self::Secret) {
         let group_generator = (self.get_group_generator)(rng);
-        let secret = rng.gen_range(BigInt::one()..T::order().into());
-        println!("secret is: {}", secret);
-        let key = group_generator.clone() * secret.clone();
+        let order = group_generator.group_order();
+        let secret = rng.gen_range(BigInt::one()..order.into());
+        let key = group_generator.clone().mul_secret(&secret);
         (
-            ElGamalPublicKey {
-                group_generator,
-                key,
-            },
+            ElGamalPublicKey::new(group_generator, key),
             ElGamalSecret {
                 secret,
                 group: PhantomData,
@@ -76,14 +97,23 @@ where
 
 impl<T> Encryptor for ElGamalPublicKey<T>
 where
-    T: FinGroup + 'static,
+    T: DynFinGroup + 'static,
 {
     fn encrypt(&self, rng: &mut dyn RngCore, message: T) -> (T, T) {
-        let y = rng.gen_range(BigInt::one()..T::order().into());
-        (
-            self.group_generator.clone() * y.clone(),
-            self.key.clone() * y + message,
-        )
+        let order = self.group_generator.group_order();
+        let y = rng.gen_range(BigInt::one()..order.into());
+        match &self.tables {
+            Some((generator, key))
+                if generator.base() == &self.group_generator
+                    && key.base() == &self.key =>
+            {
+                (generator.mul(&y), key.mul(&y) + message)
+            }
+            _ => (
+                self.group_generator.clone().mul_secret(&y),
+                self.key.clone().mul_secret(&y) + message,
+            ),
+        }
     }
 }
 
@@ -97,11 +127,39 @@ where
 
 impl<T> Decryptor for ElGamalSecret<T>
 where
-    T: FinGroup + 'static,
+    T: DynFinGroup + 'static,
 {
     type Error = Infallible;
 
     fn decrypt(&self, (salt, cipher): (T, T)) -> Result<T, Self::Error> {
-        Ok(cipher - salt * self.secret.clone())
+        Ok(cipher - salt.mul_secret(&self.secret))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+    use rand::{thread_rng, RngCore};
+
+    use super::ElGamal;
+    use crate::{
+        ecges::generator,
+        encryption::base::encryption::{
+            Decryptor, Encryptor, PublicKeyEncryption,
+        },
+    };
+
+    #[test]
+    fn stale_tables() {
+        let mut rng = thread_rng();
+        let scheme = ElGamal::from(|_: &mut dyn RngCore| generator());
+        let (mut first, _) = scheme.generate_keys(&mut rng);
+        let (second, secret) = scheme.generate_keys(&mut rng);
+        let message = generator() * BigInt::from(5);
+        let cipher = second.encrypt(&mut rng, message.clone());
+        assert!(secret.decrypt(cipher).unwrap() == message);
+        first.key = second.key;
+        let cipher = first.encrypt(&mut rng, message.clone());
+        assert!(secret.decrypt(cipher).unwrap() == message);
     }
 }
@@ -2,7 +2,8 @@ use crate::{algebra::traits::FinGroup, encryption::base::encapsulation::*};
 use num_bigint::BigInt;
 use num_traits::One;
 use rand::{Rng, RngCore};
-use std::{convert::Infallible, marker::PhantomData};
+use std::marker::PhantomData;
+use thiserror::Error;
 
 pub struct ElGamalKem<G, F> {
     pub group_generator_gen: G,
@@ -26,7 +27,7 @@ where
     G0: Fn(&mut dyn RngCore) -> G,
     F0: Fn(&mut dyn RngCore) -> F,
     G: 'static,
-    F: Fn(G) -> K,
+    F: Fn(&G, G) -> K,
 {
     type Key = K;
     type Cipher = G;
@@ -37,7 +38,7 @@ where
     G0: Fn(&mut dyn RngCore) -> G,
     F0: Fn(&mut dyn RngCore) -> F,
     G: FinGroup + 'static,
-    F: Clone + Fn(G) -> K + 'static,
+    F: Clone + Fn(&G, G) -> K + 'static,
 {
     type Encaps = ElGamalEncaps<G, F>;
     type Decaps = ElGamalDecaps<G, F>;
@@ -49,7 +50,7 @@ where
         let group_generator = (self.group_generator_gen)(rng);
         let key_from_group = (self.key_from_group_gen)(rng);
         let secret = rng.gen_range(BigInt::one()..G::order().into());
-        let group_key = group_generator.clone() * secret.clone();
+        let group_key = group_generator.clone().mul_secret(&secret);
         (
             ElGamalEncaps {
                 group_key,
@@ -68,36 +69,41 @@ where
 impl<G, F, K> Encapsulator for ElGamalEncaps<G, F>
 where
     G: FinGroup + 'static,
-    F: Fn(G) -> K,
+    F: Fn(&G, G) -> K,
 {
     fn encapsulate(&self, rng: &mut dyn RngCore) -> (Self::Key, Self::Cipher) {
         let y = rng.gen_range(BigInt::one()..G::order().into());
-        (
-            (self.key_from_group)(self.group_key.clone() * y.clone()),
-            self.group_generator.clone() * y,
-        )
+        let cipher = self.group_generator.clone().mul_secret(&y);
+        let shared = self.group_key.clone().mul_secret(&y);
+        ((self.key_from_group)(&cipher, shared), cipher)
     }
 }
 
 impl<G, F, K> Decapsulator for ElGamalDecaps<G, F>
 where
     G: FinGroup + 'static,
-    F: Fn(G) -> K,
+    F: Fn(&G, G) -> K,
 {
-    type Error = Infallible;
+    type Error = LowOrderCipher;
 
+    /// Rejects the identity and elements outside the subgroup of the group
+    /// order, whose multiples would leak the secret modulo a small cofactor.
     fn decapsulate(
         &self,
         cipher: Self::Cipher,
     ) -> Result<Self::Key, Self::Error> {
-        Ok((self.key_from_group)(cipher * self.secret.clone()))
+        if cipher.is_zero() || !(cipher.clone() * G::order().into()).is_zero() {
+            return Err(LowOrderCipher);
+        }
+        let shared = cipher.clone().mul_secret(&self.secret);
+        Ok((self.key_from_group)(&cipher, shared))
     }
 }
 
 impl<G, F, K> Caps for ElGamalEncaps<G, F>
 where
     G: 'static,
-    F: Fn(G) -> K,
+    F: Fn(&G, G) -> K,
 {
     type Key = K;
     type Cipher = G;
@@ -106,8 +112,45 @@ where
 impl<G, F, K> Caps for ElGamalDecaps<G, F>
 where
     G: 'static,
-    F: Fn(G) -> K,
+    F: Fn(&G, G) -> K,
 {
     type Key = K;
     type Cipher = G;
 }
+
+#[derive(Debug, Error)]
+#[error("ephemeral element is not of the group order")]
+pub struct LowOrderCipher;
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+    use rand::{thread_rng, RngCore};
+
+    use super::{ElGamalKem, LowOrderCipher};
+    use crate::{
+        algebra::binary_curve::{BinaryCurve, BinaryPoint},
+        curves::koblitz::{Gf163, K163Point, K163},
+        encryption::base::encapsulation::{
+            Decapsulator, Encapsulator, KeyEncapsulation,
+        },
+    };
+
+    #[test]
+    fn low_order_cipher() {
+        let mut rng = thread_rng();
+        let kem = ElGamalKem {
+            group_generator_gen: |_: &mut dyn RngCore| K163::generator(),
+            key_from_group_gen: |_: &mut dyn RngCore| {
+                |_: &K163Point, shared: K163Point| shared
+            },
+        };
+        let (encaps, decaps) = kem.generate_caps(&mut rng);
+        let (key, cipher) = encaps.encapsulate(&mut rng);
+        assert!(decaps.decapsulate(cipher.clone()).unwrap() == key);
+        let two_torsion = K163::affine(Gf163::zero(), Gf163::one()).unwrap();
+        for cipher in [BinaryPoint::zero(), two_torsion + cipher] {
+            assert!(matches!(decaps.decapsulate(cipher), Err(LowOrderCipher)));
+        }
+    }
+}
@@ -1,14 +1,96 @@
+use std::convert::TryInto;
+
 use rand::RngCore;
 
-use self::el_gamal::ElGamal;
+use crate::{
+    algebra::traits::FinGroup,
+    bytes::{Deserialize, Serialize},
+    encryption::{
+        base::encryption::PublicKeyEncryption, extensions::hybrid_encryption,
+    },
+    hash::{hkdf::hkdf, sha256::Sha256},
+};
+
+use self::{
+    chacha20::KEY_LEN, chacha20_poly1305::ChaCha20Poly1305Key,
+    el_gamal::ElGamal, el_gamal_kem::ElGamalKem,
+};
 
+pub mod chacha20;
+pub mod chacha20_poly1305;
 pub mod el_gamal;
 pub mod el_gamal_kem;
+pub mod poly1305;
 
 pub fn el_gamal_const<T>(
     f: impl Fn() -> T,
 ) -> ElGamal<impl Fn(&mut dyn RngCore) -> T> {
-    ElGamal {
-        get_group_generator: move |_: &mut dyn RngCore| f(),
+    ElGamal::from(move |_: &mut dyn RngCore| f())
+}
+
+/// ElGamal KEM over the group of `f` with ChaCha20-Poly1305 for messages.
+pub fn ecies<T>(
+    f: impl Fn() -> T + 'static,
+) -> impl PublicKeyEncryption<Message = String, Cipher = String>
+where
+    T: FinGroup + Serialize + Deserialize + 'static,
+{
+    hybrid_encryption(ElGamalKem {
+        group_generator_gen: move |_: &mut dyn RngCore| f(),
+        key_from_group_gen: |_: &mut dyn RngCore| derive_key::<T>,
+    })
+}
+
+/// HKDF-SHA-256 of the serialized ephemeral and shared elements, so that
+/// the key is bound to the encapsulation as in ECIES-KEM.
+pub fn derive_key<T: Clone + Serialize>(
+    ephemeral: &T,
+    shared: T,
+) -> ChaCha20Poly1305Key {
+    let input = [ephemeral.clone().serialize(), shared.serialize()].concat();
+    let key = hkdf::<Sha256>(&[], &input, b"elliptic ECIES", KEY_LEN).unwrap();
+    ChaCha20Poly1305Key(key.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+    use rand::thread_rng;
+
+    use super::{derive_key, ecies};
+    use crate::{
+        algebra::curve::NamedCurve,
+        curves::nist::P256,
+        ecges::generator,
+        encryption::{
+            base::encryption::{Decryptor, Encryptor},
+            extensions::make_dyn,
+        },
+    };
+
+    #[test]
+    fn key_depends_on_ephemeral() {
+        let shared = generator() * BigInt::from(7);
+        let key = |k: u32| {
+            derive_key(&(generator() * BigInt::from(k)), shared.clone()).0
+        };
+        assert_eq!(key(2), key(2));
+        assert_ne!(key(2), key(3));
+    }
+
+    #[test]
+    fn ecies_round_trip() {
+        let mut rng = thread_rng();
+        let schemes =
+            [make_dyn(ecies(generator)), make_dyn(ecies(P256::generator))];
+        for scheme in &schemes {
+            let (enc, dec) = scheme.generate_keys(&mut rng);
+            let text = "a message longer than a single curve point".repeat(3);
+            let cipher = enc.encrypt(&mut rng, text.clone());
+            assert_eq!(dec.decrypt(cipher.clone()).unwrap(), text);
+            let mut tampered = hex::decode(&cipher).unwrap();
+            *tampered.last_mut().unwrap() ^= 1;
+            assert!(dec.decrypt(hex::encode(tampered)).is_err());
+        }
     }
 }
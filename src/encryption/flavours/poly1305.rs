@@ -0,0 +1,51 @@
+//! Poly1305 one-time authenticator from RFC 8439, section 2.5.
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+pub const KEY_LEN: usize = 32;
+pub const TAG_LEN: usize = 16;
+
+pub fn poly1305(key: &[u8; KEY_LEN], message: &[u8]) -> [u8; TAG_LEN] {
+    let mut r = key[..16].to_vec();
+    for i in &[3, 7, 11, 15] {
+        r[*i] &= 0x0f;
+    }
+    for i in &[4, 8, 12] {
+        r[*i] &= 0xfc;
+    }
+    let r = BigUint::from_bytes_le(&r);
+    let s = BigUint::from_bytes_le(&key[16..]);
+    let p = (BigUint::one() << 130) - 5u32;
+    let mut acc = BigUint::default();
+    for chunk in message.chunks(16) {
+        let n = BigUint::from_bytes_le(&[chunk, &[1]].concat());
+        acc = (acc + n) * &r % &p;
+    }
+    let mut tag = (acc + s).to_bytes_le();
+    tag.resize(TAG_LEN, 0);
+    let mut result = [0; TAG_LEN];
+    result.copy_from_slice(&tag[..TAG_LEN]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::poly1305;
+
+    /// Section 2.5.2 of RFC 8439.
+    #[test]
+    fn rfc8439() {
+        let key = hex::decode(
+            "85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b",
+        )
+        .unwrap();
+        let tag = poly1305(
+            key.as_slice().try_into().unwrap(),
+            b"Cryptographic Forum Research Group",
+        );
+        assert_eq!(hex::encode(tag), "a8061dc1305136c6c22b8baf0c0127a9");
+    }
+}
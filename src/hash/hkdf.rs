@@ -0,0 +1,101 @@
+//! HKDF from RFC 5869.
+
+use thiserror::Error;
+
+use super::{
+    hmac::{hmac, Hmac},
+    Digest,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("HKDF can output at most 255 hash lengths, {0} bytes requested")]
+pub struct TooLong(pub usize);
+
+/// Pseudorandom key from `ikm`; an empty `salt` means a zero block.
+pub fn extract<D: Digest>(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    if salt.is_empty() {
+        hmac::<D>(&vec![0; D::OUTPUT_LEN], ikm)
+    } else {
+        hmac::<D>(salt, ikm)
+    }
+}
+
+pub fn expand<D: Digest>(
+    prk: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, TooLong> {
+    if len > 255 * D::OUTPUT_LEN {
+        return Err(TooLong(len));
+    }
+    let mut result = Vec::with_capacity(len);
+    let mut block = Vec::new();
+    for counter in 1..=len.div_ceil(D::OUTPUT_LEN) as u8 {
+        let mut mac = Hmac::<D>::new(prk);
+        mac.update(&block).update(info).update(&[counter]);
+        block = mac.finalize();
+        result.extend_from_slice(&block);
+    }
+    result.truncate(len);
+    Ok(result)
+}
+
+pub fn hkdf<D: Digest>(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, TooLong> {
+    expand::<D>(&extract::<D>(salt, ikm), info, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, extract, TooLong};
+    use crate::hash::sha256::Sha256;
+
+    /// Test cases 1 to 3 from RFC 5869.
+    #[test]
+    fn rfc5869() {
+        let vectors = [
+            (
+                (0..13).collect::<Vec<u8>>(),
+                vec![0x0b; 22],
+                (0xf0..0xfa).collect::<Vec<u8>>(),
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf
+                 34007208d5b887185865",
+            ),
+            (
+                (0x60..0xb0).collect(),
+                (0..0x50).collect(),
+                (0xb0..=0xff).collect(),
+                "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c
+                 59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71
+                 cc30c58179ec3e87c14c01d5c1f3434f1d87",
+            ),
+            (
+                vec![],
+                vec![0x0b; 22],
+                vec![],
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d
+                 9d201395faa4b61a96c8",
+            ),
+        ];
+        for (salt, ikm, info, prk, okm) in &vectors {
+            let okm: String = okm.split_whitespace().collect();
+            let actual = extract::<Sha256>(salt, ikm);
+            assert_eq!(hex::encode(&actual), *prk);
+            let actual = expand::<Sha256>(&actual, info, okm.len() / 2);
+            assert_eq!(hex::encode(actual.unwrap()), okm);
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        assert_eq!(expand::<Sha256>(&[1], &[], 255 * 32).unwrap().len(), 8160);
+        assert_eq!(expand::<Sha256>(&[1], &[], 8161), Err(TooLong(8161)));
+    }
+}
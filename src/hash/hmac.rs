@@ -0,0 +1,91 @@
+//! HMAC from RFC 2104.
+
+use super::Digest;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+#[derive(Clone)]
+pub struct Hmac<D> {
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest> Hmac<D> {
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = if key.len() > D::BLOCK_LEN {
+            D::digest(key)
+        } else {
+            key.to_vec()
+        };
+        block.resize(D::BLOCK_LEN, 0);
+        let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<_>>();
+        let (mut inner, mut outer) = (D::new(), D::new());
+        inner.update(&pad(IPAD));
+        outer.update(&pad(OPAD));
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+pub fn hmac<D: Digest>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<D>::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hmac;
+    use crate::hash::{
+        sha256::Sha256,
+        sha512::{Sha384, Sha512},
+    };
+
+    /// Test cases 1, 2 and 6 from RFC 4231.
+    #[test]
+    fn rfc4231() {
+        let vectors = [
+            (
+                vec![0x0b; 20],
+                &b"Hi There"[..],
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe".to_vec(),
+                &b"what do ya want for nothing?"[..],
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                vec![0xaa; 131],
+                &b"Test Using Larger Than Block-Size Key - Hash Key First"[..],
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+        ];
+        for (key, data, mac) in &vectors {
+            assert_eq!(hex::encode(hmac::<Sha256>(key, data)), *mac);
+        }
+
+        let (key, data, _) = &vectors[0];
+        assert_eq!(
+            hex::encode(hmac::<Sha384>(key, data)),
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec6\
+             82aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6"
+        );
+        assert_eq!(
+            hex::encode(hmac::<Sha512>(key, data)),
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+             daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+        );
+    }
+}
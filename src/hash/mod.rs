@@ -0,0 +1,62 @@
+pub mod hkdf;
+pub mod hmac;
+pub mod sha256;
+pub mod sha512;
+
+/// Hash function fed incrementally.
+pub trait Digest: Clone {
+    const OUTPUT_LEN: usize;
+
+    /// Input block length, used by [`hmac`] to pad keys.
+    const BLOCK_LEN: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]) -> &mut Self;
+    fn finalize(self) -> Vec<u8>;
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// Input buffering and Merkle-Damgard padding shared by SHA-2 functions.
+#[derive(Clone)]
+struct Blocks {
+    block_len: usize,
+    buffer: Vec<u8>,
+    length: u128,
+}
+
+impl Blocks {
+    fn new(block_len: usize) -> Self {
+        Self {
+            block_len,
+            buffer: Vec::with_capacity(block_len),
+            length: 0,
+        }
+    }
+
+    /// Appends `data`, passing every completed block to `compress`.
+    fn update(&mut self, data: &[u8], mut compress: impl FnMut(&[u8])) {
+        self.length += data.len() as u128;
+        self.buffer.extend_from_slice(data);
+        let full = self.buffer.len() / self.block_len * self.block_len;
+        for block in self.buffer[..full].chunks_exact(self.block_len) {
+            compress(block);
+        }
+        self.buffer.drain(..full);
+    }
+
+    /// Pads with `0x80`, zeros and the bit length in `length_len` bytes.
+    fn finish(mut self, length_len: usize, mut compress: impl FnMut(&[u8])) {
+        let bits = (self.length * 8).to_be_bytes();
+        let used = (self.buffer.len() + 1 + length_len) % self.block_len;
+        let mut padding = vec![0x80];
+        padding.resize(1 + (self.block_len - used) % self.block_len, 0);
+        padding.extend_from_slice(&bits[bits.len() - length_len..]);
+        self.update(&padding, &mut compress);
+        debug_assert!(self.buffer.is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+//! SHA-512 and SHA-384 from FIPS 180-4.
+
+use std::convert::TryInto;
+
+use super::{Blocks, Digest};
+
+pub const OUTPUT_LEN: usize = 64;
+
+const BLOCK_LEN: usize = 128;
+
+const K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+const INITIAL: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const INITIAL_384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+/// Incremental SHA-512 hasher.
+#[derive(Clone)]
+pub struct Sha512(Engine);
+
+/// SHA-512 with another initial state, truncated to 48 bytes.
+#[derive(Clone)]
+pub struct Sha384(Engine);
+
+#[derive(Clone)]
+struct Engine {
+    state: [u64; 8],
+    blocks: Blocks,
+}
+
+impl Engine {
+    fn new(state: [u64; 8]) -> Self {
+        Self {
+            state,
+            blocks: Blocks::new(BLOCK_LEN),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(self, len: usize) -> Vec<u8> {
+        let mut state = self.state;
+        self.blocks.finish(16, |block| compress(&mut state, block));
+        let mut result: Vec<u8> =
+            state.iter().flat_map(|word| word.to_be_bytes()).collect();
+        result.truncate(len);
+        result
+    }
+}
+
+impl Sha512 {
+    pub fn new() -> Self {
+        Self(Engine::new(INITIAL))
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.0.update(data);
+        self
+    }
+
+    pub fn finalize(self) -> [u8; OUTPUT_LEN] {
+        self.0.finalize(OUTPUT_LEN).try_into().unwrap()
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn sha512(data: &[u8]) -> [u8; OUTPUT_LEN] {
+    let mut hasher = Sha512::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+impl Digest for Sha512 {
+    const OUTPUT_LEN: usize = OUTPUT_LEN;
+    const BLOCK_LEN: usize = BLOCK_LEN;
+
+    fn new() -> Self {
+        Sha512::new()
+    }
+
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        Sha512::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha512::finalize(self).to_vec()
+    }
+}
+
+impl Digest for Sha384 {
+    const OUTPUT_LEN: usize = 48;
+    const BLOCK_LEN: usize = BLOCK_LEN;
+
+    fn new() -> Self {
+        Self(Engine::new(INITIAL_384))
+    }
+
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.0.update(data);
+        self
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::OUTPUT_LEN)
+    }
+}
+
+fn compress(state: &mut [u64; 8], block: &[u8]) {
+    let mut w = [0u64; 80];
+    for (word, chunk) in w.iter_mut().zip(block.chunks_exact(8)) {
+        *word = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1)
+            ^ w[i - 15].rotate_right(8)
+            ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19)
+            ^ w[i - 2].rotate_right(61)
+            ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (word, value) in state.iter_mut().zip(&[a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sha512, Sha384, Sha512};
+    use crate::hash::Digest;
+
+    const LONG: &str =
+        "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+         hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+    #[test]
+    fn vectors() {
+        let vectors = [
+            (
+                "",
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce
+                 47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            ),
+            (
+                "abc",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            ),
+            (
+                LONG,
+                "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018
+                 501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909",
+            ),
+        ];
+        for &(message, digest) in &vectors {
+            let digest: String = digest.split_whitespace().collect();
+            assert_eq!(hex::encode(sha512(message.as_bytes())), digest);
+        }
+    }
+
+    #[test]
+    fn sha384() {
+        let vectors = [
+            (
+                "",
+                "38b060a751ac96384cd9327eb1b1e36a21fdb71114be0743
+                 4c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b",
+            ),
+            (
+                "abc",
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded163
+                 1a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+            ),
+            (
+                LONG,
+                "09330c33f71147e83d192fc782cd1b4753111b173b3b05d2
+                 2fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039",
+            ),
+        ];
+        for &(message, digest) in &vectors {
+            let digest: String = digest.split_whitespace().collect();
+            assert_eq!(hex::encode(Sha384::digest(message.as_bytes())), digest);
+        }
+    }
+
+    #[test]
+    fn incremental() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+        for split in &[0, 1, 111, 112, 128, 500, 1000] {
+            let mut hasher = Sha512::new();
+            hasher.update(&data[..*split]).update(&data[*split..]);
+            assert_eq!(hasher.finalize(), sha512(&data));
+            let mut hasher = <Sha512 as Digest>::new();
+            Digest::update(&mut hasher, &data[..*split]);
+            Digest::update(&mut hasher, &data[*split..]);
+            assert_eq!(Digest::finalize(hasher), sha512(&data).to_vec());
+        }
+    }
+}
@@ -1,18 +1,33 @@
-use std::io::{stdin, Read};
+use std::{
+    fs,
+    io::{stdin, Read},
+};
 
 use clap::clap_app;
-use dlies::zp_encryption;
+use dlies::{zp_encryption, Z2048Fixed, Z2048};
 use rand::thread_rng;
 
 use crate::{
-    attack::crack, ecges::ec_encryptor, encryption::extensions::PublicEncObject,
+    algebra::{curve::NamedCurve, dyn_curve::DynCurve},
+    attack::crack,
+    curves::nist::P256,
+    ecges::{dyn_encryptor, ec_encryptor, generator},
+    encryption::{
+        extensions::{make_dyn, PublicEncObject},
+        flavours::ecies,
+    },
+    signature::{extensions::SignatureObject, flavours::p256_ecdsa},
 };
 
 pub mod algebra;
 pub mod bytes;
+pub mod curves;
 pub mod encryption;
+pub mod hash;
+pub mod signature;
 
 mod attack;
+mod bench;
 mod dlies;
 mod ecges;
 
@@ -21,27 +36,68 @@ fn main() {
         (version: "0.1")
         (author: "Pavel Sokolov <sokolov.p64@gmail.com>")
         (about: "Elliptic curves POC")
-        (@arg CURVE: -c --curve conflicts_with[PRIME CRACK] "Encrypt text with p224 curve (default)")
+        (@arg CURVE: -c --curve conflicts_with[PRIME CRACK CURVE_FILE] "Encrypt text with p224 curve (default)")
+        (@arg CURVE_FILE: -f --("curve-file") +takes_value conflicts_with[PRIME CRACK] "Encrypt text with a curve read from file")
         (@arg PRIME: -p --prime conflicts_with[CRACK] "Encrypt text with Z_{big prime} field")
+        (@arg FIXED: --fixed requires[PRIME] "Use stack-allocated residues with --prime")
         (@arg CRACK: -C --crack "Crack small groups")
+        (@arg BENCH: -b --bench conflicts_with[CURVE CURVE_FILE PRIME CRACK] "Time field arithmetic with every residue type")
+        (@arg ECIES: -e --ecies +takes_value possible_value[p224 p256] conflicts_with[CURVE CURVE_FILE PRIME CRACK] "Encrypt text with ECIES over the given curve")
+        (@arg SIGN: -s --sign conflicts_with[CURVE CURVE_FILE PRIME CRACK] "Sign text with p256 ECDSA and verify the signature")
     ).get_matches();
 
     if matches.is_present("PRIME") {
-        enc_test(zp_encryption());
+        if matches.is_present("FIXED") {
+            enc_test(zp_encryption::<Z2048Fixed>());
+        } else {
+            enc_test(zp_encryption::<Z2048>());
+        }
+    } else if let Some(curve) = matches.value_of("ECIES") {
+        match curve {
+            "p256" => enc_test(make_dyn(ecies(P256::generator))),
+            _ => enc_test(make_dyn(ecies(generator))),
+        }
+    } else if matches.is_present("SIGN") {
+        sign_test(p256_ecdsa());
     } else if matches.is_present("CRACK") {
         crack();
+    } else if matches.is_present("BENCH") {
+        bench::fields();
+    } else if let Some(path) = matches.value_of("CURVE_FILE") {
+        match fs::read_to_string(path) {
+            Ok(text) => match text.parse::<DynCurve>() {
+                Ok(curve) => enc_test(dyn_encryptor(curve.into())),
+                Err(err) => eprintln!("{}", err),
+            },
+            Err(err) => eprintln!("{}: {}", path, err),
+        }
     } else {
         enc_test(ec_encryptor());
     }
 }
 
-fn enc_test(enc: PublicEncObject) {
-    let (enc, dec) = enc.generate_keys(&mut thread_rng());
+fn read_text() -> String {
     let mut text = Vec::new();
     stdin().lock().read_to_end(&mut text).unwrap();
-    let text = String::from_utf8(text).unwrap();
+    String::from_utf8(text).unwrap()
+}
+
+fn enc_test(enc: PublicEncObject) {
+    let (enc, dec) = enc.generate_keys(&mut thread_rng());
+    let text = read_text();
     let cipher = enc.encrypt(&mut thread_rng(), text);
     println!("cipher: {}", cipher.clone());
     let text = dec.decrypt(cipher).unwrap();
     println!("decrypted text: {}", text);
 }
+
+fn sign_test(scheme: SignatureObject) {
+    let (verifier, signer) = scheme.generate_keys(&mut thread_rng());
+    let text = read_text();
+    let signature = signer.sign(&mut thread_rng(), text.clone());
+    println!("signature: {}", signature.clone());
+    match verifier.verify(text, signature) {
+        Ok(()) => println!("signature is valid"),
+        Err(err) => println!("signature is invalid: {}", err),
+    }
+}
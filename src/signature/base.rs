@@ -0,0 +1,65 @@
+use std::{error::Error, ops::Deref};
+
+use rand::RngCore;
+
+pub trait Sig {
+    type Message: 'static;
+    type Signature: 'static;
+}
+
+pub trait SignatureScheme: Sig {
+    type Verifier: Verifier<Message = Self::Message, Signature = Self::Signature>
+        + 'static;
+    type Signer: Signer<Message = Self::Message, Signature = Self::Signature>
+        + 'static;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Verifier, Self::Signer);
+}
+
+pub trait Signer: Sig {
+    fn sign(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature;
+}
+
+pub trait Verifier: Sig {
+    type Error: Error + 'static;
+
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+impl<S: Sig + ?Sized> Sig for Box<S> {
+    type Message = S::Message;
+    type Signature = S::Signature;
+}
+
+impl<S: Signer + ?Sized> Signer for Box<S> {
+    fn sign(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature {
+        self.deref().sign(rng, message)
+    }
+}
+
+impl<S: Verifier + ?Sized> Verifier for Box<S> {
+    type Error = S::Error;
+
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error> {
+        self.deref().verify(message, signature)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{
+    bytes::{Deserialize, Serialize},
+    encryption::extensions::object::DynError,
+};
+
+use self::{object::DynSignature, stringer::Stringer};
+
+use super::base::{SignatureScheme, Signer, Verifier};
+
+pub mod object;
+pub mod stringer;
+
+pub type VerifierObject =
+    Box<dyn Verifier<Message = String, Signature = String, Error = DynError>>;
+
+pub type SignerObject = Box<dyn Signer<Message = String, Signature = String>>;
+
+pub type SignatureObject = Box<
+    dyn SignatureScheme<
+        Message = String,
+        Signature = String,
+        Verifier = VerifierObject,
+        Signer = SignerObject,
+    >,
+>;
+
+pub fn string_signature<S>(
+    scheme: S,
+) -> impl SignatureScheme<Message = String, Signature = String>
+where
+    S: SignatureScheme<Message = Vec<u8>> + 'static,
+    S::Signature: Serialize + Deserialize,
+{
+    Stringer(scheme)
+}
+
+pub fn make_dyn(
+    scheme: impl SignatureScheme<Message = String, Signature = String> + 'static,
+) -> SignatureObject {
+    Box::new(DynSignature(scheme))
+}
@@ -0,0 +1,54 @@
+use rand::RngCore;
+
+use crate::{encryption::extensions::object::DynError, signature::base::*};
+
+pub struct DynSignature<X>(pub X);
+
+impl<X: Sig> Sig for DynSignature<X> {
+    type Message = X::Message;
+    type Signature = X::Signature;
+}
+
+impl<X: SignatureScheme> SignatureScheme for DynSignature<X> {
+    type Verifier = Box<
+        dyn Verifier<
+            Message = Self::Message,
+            Signature = Self::Signature,
+            Error = DynError,
+        >,
+    >;
+    type Signer =
+        Box<dyn Signer<Message = Self::Message, Signature = Self::Signature>>;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Verifier, Self::Signer) {
+        let (verifier, signer) = self.0.generate_keys(rng);
+        (Box::new(DynSignature(verifier)), Box::new(signer))
+    }
+}
+
+impl<X: Signer> Signer for DynSignature<X> {
+    fn sign(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature {
+        self.0.sign(rng, message)
+    }
+}
+
+impl<X: Verifier> Verifier for DynSignature<X> {
+    type Error = DynError;
+
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error> {
+        self.0
+            .verify(message, signature)
+            .map_err(|err| DynError(Box::new(err)))
+    }
+}
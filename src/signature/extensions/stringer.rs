@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use hex::FromHexError;
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::{bytes::*, signature::base::*};
+
+/// Signs strings and writes signatures as hex.
+pub struct Stringer<X>(pub X);
+
+impl<X> Sig for Stringer<X> {
+    type Message = String;
+    type Signature = String;
+}
+
+impl<X> SignatureScheme for Stringer<X>
+where
+    X: SignatureScheme<Message = Vec<u8>>,
+    X::Signature: Serialize + Deserialize,
+{
+    type Verifier = Stringer<X::Verifier>;
+    type Signer = Stringer<X::Signer>;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Verifier, Self::Signer) {
+        let (verifier, signer) = self.0.generate_keys(rng);
+        (Stringer(verifier), Stringer(signer))
+    }
+}
+
+impl<X> Signer for Stringer<X>
+where
+    X: Signer<Message = Vec<u8>>,
+    X::Signature: Serialize,
+{
+    fn sign(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature {
+        hex::encode(self.0.sign(rng, message.into_bytes()).serialize())
+    }
+}
+
+impl<X> Verifier for Stringer<X>
+where
+    X: Verifier<Message = Vec<u8>>,
+    X::Signature: Deserialize,
+{
+    type Error = StringVerificationError<
+        ConsumeDeserError<<X::Signature as Deserialize>::Error>,
+        X::Error,
+    >;
+
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error> {
+        let signature = consume_deser(hex::decode(signature)?)
+            .map_err(Self::Error::Deserialization)?;
+        self.0
+            .verify(message.into_bytes(), signature)
+            .map_err(Self::Error::Verification)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum StringVerificationError<S: Error + 'static, V: Error + 'static> {
+    #[error("Signature is not a hex string")]
+    NotAHex(#[from] FromHexError),
+    #[error("Deserialization error")]
+    Deserialization(#[source] S),
+    #[error("Verification error")]
+    Verification(#[source] V),
+}
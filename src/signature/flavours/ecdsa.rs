@@ -0,0 +1,700 @@
+//! ECDSA from FIPS 186-4 with nonces from RFC 6979.
+
+use std::marker::PhantomData;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::{
+    algebra::{
+        curve::{EllipticPoint, NamedCurve},
+        sec1::field_len,
+        traits::{multi_scalar_mul, Field, FinGroup},
+    },
+    bytes::{Deserialize, Serialize},
+    hash::Digest,
+    signature::{
+        base::{Sig, SignatureScheme, Signer, Verifier},
+        rfc6979::{bits2int, Nonces},
+    },
+};
+
+/// ECDSA on `C` with messages hashed by `D`.
+pub struct Ecdsa<F, C, D> {
+    low_s: bool,
+    params: PhantomData<(F, C, D)>,
+}
+
+impl<F, C, D> Ecdsa<F, C, D> {
+    pub fn new() -> Self {
+        Self {
+            low_s: false,
+            params: PhantomData,
+        }
+    }
+
+    /// Generates keys that sign and accept only low-s signatures.
+    pub fn low_s(self) -> Self {
+        Self {
+            low_s: true,
+            ..self
+        }
+    }
+}
+
+impl<F, C, D> Default for Ecdsa<F, C, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EcdsaError {
+    #[error("secret or public key is out of range")]
+    InvalidKey,
+    #[error("signature component is out of range")]
+    OutOfRange,
+    #[error("signature is not in low-s form")]
+    HighS,
+    #[error("signature does not match")]
+    Mismatch,
+    #[error("malformed DER signature")]
+    MalformedDer,
+    #[error("fixed-width signature has odd length")]
+    MalformedBytes,
+}
+
+/// Pair `(r, s)` with both components in `[1, n)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Signature {
+    /// Replaces `s` with `n - s` if it is above `n / 2`.
+    pub fn normalize_s(self, order: &BigUint) -> Self {
+        if self.is_low_s(order) {
+            self
+        } else {
+            Self {
+                s: order - self.s,
+                ..self
+            }
+        }
+    }
+
+    pub fn is_low_s(&self, order: &BigUint) -> bool {
+        self.s <= order >> 1
+    }
+
+    /// `r || s`, each padded to `len` bytes.
+    pub fn to_bytes(&self, len: usize) -> Vec<u8> {
+        let pad = |value: &BigUint| {
+            let bytes = value.to_bytes_be();
+            let mut result = vec![0; len.saturating_sub(bytes.len())];
+            result.extend_from_slice(&bytes);
+            result
+        };
+        [pad(&self.r), pad(&self.s)].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(EcdsaError::MalformedBytes);
+        }
+        let (r, s) = bytes.split_at(bytes.len() / 2);
+        Ok(Self {
+            r: BigUint::from_bytes_be(r),
+            s: BigUint::from_bytes_be(s),
+        })
+    }
+
+    /// `SEQUENCE { INTEGER r, INTEGER s }` in DER.
+    pub fn to_der(&self) -> Vec<u8> {
+        let contents = [der_integer(&self.r), der_integer(&self.s)].concat();
+        der_tlv(0x30, &contents)
+    }
+
+    pub fn from_der(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        let (contents, rest) = read_tlv(0x30, bytes)?;
+        if !rest.is_empty() {
+            return Err(EcdsaError::MalformedDer);
+        }
+        let (r, contents) = read_integer(contents)?;
+        let (s, contents) = read_integer(contents)?;
+        if contents.is_empty() {
+            Ok(Self { r, s })
+        } else {
+            Err(EcdsaError::MalformedDer)
+        }
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize(self) -> Vec<u8> {
+        self.to_der()
+    }
+}
+
+impl Deserialize for Signature {
+    type Error = EcdsaError;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        let mut bytes = match stream.next() {
+            Some(tag) => vec![tag],
+            None => return Ok(None),
+        };
+        let mut len = stream.next().ok_or(EcdsaError::MalformedDer)?;
+        bytes.push(len);
+        if len == 0x81 {
+            len = stream.next().ok_or(EcdsaError::MalformedDer)?;
+            bytes.push(len);
+        }
+        bytes.extend(stream.take(len as usize));
+        Self::from_der(&bytes).map(Some)
+    }
+}
+
+fn der_tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut result = vec![tag];
+    match contents.len() {
+        len @ 0..=0x7f => result.push(len as u8),
+        len @ 0x80..=0xff => result.extend_from_slice(&[0x81, len as u8]),
+        len => panic!("DER contents of {} bytes are not supported", len),
+    }
+    result.extend_from_slice(contents);
+    result
+}
+
+fn der_integer(value: &BigUint) -> Vec<u8> {
+    let mut bytes = value.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    der_tlv(0x02, &bytes)
+}
+
+/// Contents of a TLV with the given tag and the bytes after it.
+fn read_tlv(tag: u8, bytes: &[u8]) -> Result<(&[u8], &[u8]), EcdsaError> {
+    let (len, header) = match bytes {
+        [t, 0x81, len, ..] if *t == tag && *len >= 0x80 => (*len, 3),
+        [t, len, ..] if *t == tag && *len < 0x80 => (*len, 2),
+        _ => return Err(EcdsaError::MalformedDer),
+    };
+    let end = header + len as usize;
+    if bytes.len() < end {
+        return Err(EcdsaError::MalformedDer);
+    }
+    Ok((&bytes[header..end], &bytes[end..]))
+}
+
+/// Minimally encoded non-negative INTEGER.
+fn read_integer(bytes: &[u8]) -> Result<(BigUint, &[u8]), EcdsaError> {
+    let (contents, rest) = read_tlv(0x02, bytes)?;
+    match contents {
+        [] => Err(EcdsaError::MalformedDer),
+        [first, ..] if first & 0x80 != 0 => Err(EcdsaError::MalformedDer),
+        [0, second, ..] if second & 0x80 == 0 => Err(EcdsaError::MalformedDer),
+        _ => Ok((BigUint::from_bytes_be(contents), rest)),
+    }
+}
+
+/// Byte length of each half of a fixed-width signature on `C`.
+pub fn scalar_len<F: Field, C: NamedCurve<F>>() -> usize {
+    field_len(&C::group_order())
+}
+
+pub struct SigningKey<F, C, D> {
+    secret: BigUint,
+    public: EllipticPoint<F, C>,
+    low_s: bool,
+    hash: PhantomData<D>,
+}
+
+impl<F, C, D> SigningKey<F, C, D>
+where
+    F: Field,
+    C: NamedCurve<F>,
+    BigUint: From<F>,
+    D: Digest,
+{
+    pub fn new(secret: BigUint) -> Result<Self, EcdsaError> {
+        if secret.is_zero() || secret >= C::group_order() {
+            return Err(EcdsaError::InvalidKey);
+        }
+        let public = C::generator().mul_secret(&secret.clone().into());
+        Ok(Self {
+            secret,
+            public,
+            low_s: false,
+            hash: PhantomData,
+        })
+    }
+
+    /// Makes [`SigningKey::sign`] produce only low-s signatures.
+    pub fn low_s(self) -> Self {
+        Self {
+            low_s: true,
+            ..self
+        }
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey<F, C, D> {
+        VerifyingKey {
+            public: self.public.clone(),
+            low_s: self.low_s,
+            hash: PhantomData,
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let order = C::group_order();
+        let digest = D::digest(message);
+        let e = bits2int(&digest, order.bits());
+        for k in Nonces::<D>::new(&self.secret, &digest, &order) {
+            let point = C::generator().mul_secret(&k.clone().into());
+            let x = match Option::<(F, F)>::from(point) {
+                Some((x, _)) => BigUint::from(x),
+                None => continue,
+            };
+            let r = x % &order;
+            if r.is_zero() {
+                continue;
+            }
+            let k_inv = k.modpow(&(&order - 2u32), &order);
+            let s = k_inv * (&e + &r * &self.secret) % &order;
+            if s.is_zero() {
+                continue;
+            }
+            let signature = Signature { r, s };
+            return if self.low_s {
+                signature.normalize_s(&order)
+            } else {
+                signature
+            };
+        }
+        unreachable!("nonce generator is infinite")
+    }
+}
+
+pub struct VerifyingKey<F, C, D> {
+    public: EllipticPoint<F, C>,
+    low_s: bool,
+    hash: PhantomData<D>,
+}
+
+impl<F, C, D> VerifyingKey<F, C, D>
+where
+    F: Field,
+    C: NamedCurve<F>,
+    BigUint: From<F>,
+    D: Digest,
+{
+    pub fn new(public: EllipticPoint<F, C>) -> Result<Self, EcdsaError> {
+        if public.is_zero()
+            || !(public.clone() * C::group_order().into()).is_zero()
+        {
+            return Err(EcdsaError::InvalidKey);
+        }
+        Ok(Self {
+            public,
+            low_s: false,
+            hash: PhantomData,
+        })
+    }
+
+    /// Rejects signatures with `s` above `n / 2`.
+    pub fn low_s(self) -> Self {
+        Self {
+            low_s: true,
+            ..self
+        }
+    }
+
+    pub fn public(&self) -> &EllipticPoint<F, C> {
+        &self.public
+    }
+
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), EcdsaError> {
+        let order = C::group_order();
+        let Signature { r, s } = signature;
+        let in_range = |value: &BigUint| !value.is_zero() && value < &order;
+        if !in_range(r) || !in_range(s) {
+            return Err(EcdsaError::OutOfRange);
+        }
+        if self.low_s && !signature.is_low_s(&order) {
+            return Err(EcdsaError::HighS);
+        }
+        let e = bits2int(&D::digest(message), order.bits());
+        let w = s.modpow(&(&order - 2u32), &order);
+        let u1 = e * &w % &order;
+        let u2 = r * &w % &order;
+        let point = multi_scalar_mul(
+            &[C::generator(), self.public.clone()],
+            &[u1.into(), u2.into()],
+        );
+        let x = Option::<(F, F)>::from(point).map(|(x, _)| BigUint::from(x));
+        if x.map(|x| x % &order).as_ref() == Some(r) {
+            Ok(())
+        } else {
+            Err(EcdsaError::Mismatch)
+        }
+    }
+}
+
+impl<F: Clone, C, D> Clone for VerifyingKey<F, C, D> {
+    fn clone(&self) -> Self {
+        Self {
+            public: self.public.clone(),
+            low_s: self.low_s,
+            hash: PhantomData,
+        }
+    }
+}
+
+impl<F, C, D> Sig for Ecdsa<F, C, D> {
+    type Message = Vec<u8>;
+    type Signature = Signature;
+}
+
+impl<F, C, D> SignatureScheme for Ecdsa<F, C, D>
+where
+    F: Field + 'static,
+    C: NamedCurve<F> + 'static,
+    BigUint: From<F>,
+    D: Digest + 'static,
+{
+    type Verifier = VerifyingKey<F, C, D>;
+    type Signer = SigningKey<F, C, D>;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Verifier, Self::Signer) {
+        let secret = rng.gen_biguint_range(&BigUint::one(), &C::group_order());
+        let mut signer = SigningKey::new(secret).unwrap();
+        signer.low_s = self.low_s;
+        (signer.verifying_key(), signer)
+    }
+}
+
+impl<F, C, D> Sig for SigningKey<F, C, D> {
+    type Message = Vec<u8>;
+    type Signature = Signature;
+}
+
+impl<F, C, D> Signer for SigningKey<F, C, D>
+where
+    F: Field,
+    C: NamedCurve<F>,
+    BigUint: From<F>,
+    D: Digest,
+{
+    /// Nonces are deterministic, so `rng` is not used.
+    fn sign(
+        &self,
+        _: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature {
+        SigningKey::sign(self, &message)
+    }
+}
+
+impl<F, C, D> Sig for VerifyingKey<F, C, D> {
+    type Message = Vec<u8>;
+    type Signature = Signature;
+}
+
+impl<F, C, D> Verifier for VerifyingKey<F, C, D>
+where
+    F: Field,
+    C: NamedCurve<F>,
+    BigUint: From<F>,
+    D: Digest,
+{
+    type Error = EcdsaError;
+
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error> {
+        VerifyingKey::verify(self, &message, &signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use rand::thread_rng;
+
+    use super::{
+        scalar_len, Ecdsa, EcdsaError, Signature, SigningKey, VerifyingKey,
+    };
+    use crate::{
+        algebra::{
+            curve::{Curve, NamedCurve},
+            traits::Field,
+        },
+        bytes::{consume_deser, Serialize},
+        curves::nist::{P256, Z256},
+        ecges::{hex, P224, Z224},
+        hash::{
+            sha256::{Sha224, Sha256},
+            sha512::Sha512,
+            Digest,
+        },
+        signature::{
+            base::{SignatureScheme, Signer, Verifier},
+            extensions::{make_dyn, string_signature},
+        },
+    };
+
+    const P224_SECRET: &str =
+        "F220266E1105BFE3083E03EC7A3A654651F45E37167E88600BF257C1";
+    const P224_PUBLIC: (&str, &str) = (
+        "00CF08DA5AD719E42707FA431292DEA11244D64FC51610D94B130D6C",
+        "EEAB6F3DEBE455E3DBF85416F7030CBD94F34F2D6F232C69F3C1385A",
+    );
+    const P256_SECRET: &str =
+        "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721";
+    const P256_PUBLIC: (&str, &str) = (
+        "60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6",
+        "7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299",
+    );
+
+    /// Checks `(message, r, s)` vectors from RFC 6979, appendix A.2.
+    fn check<F, C, D>(
+        secret: &str,
+        public: (&str, &str),
+        vectors: &[(&str, &str, &str)],
+    ) where
+        F: Field,
+        C: NamedCurve<F>,
+        BigUint: From<F>,
+        D: Digest,
+    {
+        let key = SigningKey::<F, C, D>::new(hex(secret)).unwrap();
+        let verifying = key.verifying_key();
+        let (x, y) = verifying.public().clone().into();
+        assert_eq!(BigUint::from(x), hex(public.0));
+        assert_eq!(BigUint::from(y), hex(public.1));
+        let low_key = SigningKey::<F, C, D>::new(hex(secret)).unwrap().low_s();
+        let strict = verifying.clone().low_s();
+        let order = C::group_order();
+        for &(message, r, s) in vectors {
+            let message = message.as_bytes();
+            let expected = Signature {
+                r: hex(r),
+                s: hex(s),
+            };
+            assert_eq!(key.sign(message), expected);
+            assert_eq!(verifying.verify(message, &expected), Ok(()));
+            assert_eq!(
+                verifying.verify(b"other", &expected),
+                Err(EcdsaError::Mismatch)
+            );
+            let low = low_key.sign(message);
+            assert_eq!(low, expected.clone().normalize_s(&order));
+            assert_eq!(strict.verify(message, &low), Ok(()));
+            if low != expected {
+                assert_eq!(
+                    strict.verify(message, &expected),
+                    Err(EcdsaError::HighS)
+                );
+            }
+            let der = expected.to_der();
+            assert_eq!(Signature::from_der(&der), Ok(expected.clone()));
+            let bytes = expected.to_bytes(scalar_len::<F, C>());
+            assert_eq!(bytes.len(), 2 * scalar_len::<F, C>());
+            assert_eq!(Signature::from_bytes(&bytes), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn p224() {
+        check::<Z224, P224, Sha224>(
+            P224_SECRET,
+            P224_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "1CDFE6662DDE1E4A1EC4CDEDF6A1F5A2FB7FBD9145C12113E6ABFD3E",
+                    "A6694FD7718A21053F225D3F46197CA699D45006C06F871808F43EBC",
+                ),
+                (
+                    "test",
+                    "C441CE8E261DED634E4CF84910E4C5D1D22C5CF3B732BB204DBEF019",
+                    "902F42847A63BDC5F6046ADA114953120F99442D76510150F372A3F4",
+                ),
+            ],
+        );
+        check::<Z224, P224, Sha256>(
+            P224_SECRET,
+            P224_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "61AA3DA010E8E8406C656BC477A7A7189895E7E840CDFE8FF42307BA",
+                    "BC814050DAB5D23770879494F9E0A680DC1AF7161991BDE692B10101",
+                ),
+                (
+                    "test",
+                    "AD04DDE87B84747A243A631EA47A1BA6D1FAA059149AD2440DE6FBA6",
+                    "178D49B1AE90E3D8B629BE3DB5683915F4E8C99FDF6E666CF37ADCFD",
+                ),
+            ],
+        );
+        check::<Z224, P224, Sha512>(
+            P224_SECRET,
+            P224_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "074BD1D979D5F32BF958DDC61E4FB4872ADCAFEB2256497CDAC30397",
+                    "A4CECA196C3D5A1FF31027B33185DC8EE43F288B21AB342E5D8EB084",
+                ),
+                (
+                    "test",
+                    "049F050477C5ADD858CAC56208394B5A55BAEBBE887FDF765047C17C",
+                    "077EB13E7005929CEFA3CD0403C7CDCC077ADF4E44F3C41B2F60ECFF",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn p256() {
+        check::<Z256, P256, Sha224>(
+            P256_SECRET,
+            P256_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "53B2FFF5D1752B2C689DF257C04C40A587FABABB3F6FC2702F1343AF7CA9AA3F",
+                    "B9AFB64FDC03DC1A131C7D2386D11E349F070AA432A4ACC918BEA988BF75C74C",
+                ),
+                (
+                    "test",
+                    "C37EDB6F0AE79D47C3C27E962FA269BB4F441770357E114EE511F662EC34A692",
+                    "C820053A05791E521FCAAD6042D40AEA1D6B1A540138558F47D0719800E18F2D",
+                ),
+            ],
+        );
+        check::<Z256, P256, Sha256>(
+            P256_SECRET,
+            P256_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716",
+                    "F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8",
+                ),
+                (
+                    "test",
+                    "F1ABB023518351CD71D881567B1EA663ED3EFCF6C5132B354F28D3B0B7D38367",
+                    "019F4113742A2B14BD25926B49C649155F267E60D3814B4C0CC84250E46F0083",
+                ),
+            ],
+        );
+        check::<Z256, P256, Sha512>(
+            P256_SECRET,
+            P256_PUBLIC,
+            &[
+                (
+                    "sample",
+                    "8496A60B5E9B47C825488827E0495B0E3FA109EC4568FD3F8D1097678EB97F00",
+                    "2362AB1ADBE2B8ADF9CB9EDAB740EA6049C028114F2460F96554F61FAE3302FE",
+                ),
+                (
+                    "test",
+                    "461D93F31B6540894788FD206C07CFA0CC35F46FA3C91816FFF1040AD1581A04",
+                    "39AF9F15DE0DB8D97E72719C74820D304CE5226E32DEDAE67519E840D1194E55",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn der() {
+        let signature = Signature {
+            r: BigUint::from(0x80u32),
+            s: BigUint::from(0x7fu32),
+        };
+        let der = signature.to_der();
+        assert_eq!(der, [0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x7f]);
+        let malformed: [&[u8]; 5] = [
+            &[0x30, 0x06, 0x02, 0x01, 0x80, 0x02, 0x01, 0x7f],
+            &[0x30, 0x08, 0x02, 0x02, 0x00, 0x7f, 0x02, 0x01, 0x7f, 0x00],
+            &[0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x00],
+            &der[..8],
+            &[der.as_slice(), &[0]].concat(),
+        ];
+        for bytes in &malformed {
+            assert_eq!(
+                Signature::from_der(bytes),
+                Err(EcdsaError::MalformedDer)
+            );
+        }
+        assert_eq!(
+            Signature::from_bytes(&[1, 2, 3]),
+            Err(EcdsaError::MalformedBytes)
+        );
+    }
+
+    #[test]
+    fn rejects() {
+        let order = <P224 as Curve<Z224>>::group_order();
+        assert!(SigningKey::<Z224, P224, Sha256>::new(order.clone()).is_err());
+        let key =
+            SigningKey::<Z224, P224, Sha256>::new(hex(P224_SECRET)).unwrap();
+        let verifying = key.verifying_key();
+        let signature = key.sign(b"sample");
+        for (r, s) in [
+            (BigUint::from(0u32), signature.s.clone()),
+            (signature.r.clone(), order.clone()),
+        ] {
+            assert_eq!(
+                verifying.verify(b"sample", &Signature { r, s }),
+                Err(EcdsaError::OutOfRange)
+            );
+        }
+        let zero = P224::generator() * 0.into();
+        assert!(VerifyingKey::<Z224, P224, Sha256>::new(zero).is_err());
+    }
+
+    #[test]
+    fn scheme() {
+        let mut rng = thread_rng();
+        let scheme = Ecdsa::<Z256, P256, Sha256>::new().low_s();
+        let (verifier, signer) = scheme.generate_keys(&mut rng);
+        let message = b"message".to_vec();
+        let signature = Signer::sign(&signer, &mut rng, message.clone());
+        assert!(signature.is_low_s(&<P256 as Curve<Z256>>::group_order()));
+        let bytes = signature.clone().serialize();
+        assert_eq!(consume_deser::<Signature>(bytes).unwrap(), signature);
+        assert!(Verifier::verify(&verifier, message, signature).is_ok());
+
+        let scheme =
+            make_dyn(string_signature(Ecdsa::<Z256, P256, Sha256>::new()));
+        let (verifier, signer) = scheme.generate_keys(&mut rng);
+        let signature = signer.sign(&mut rng, "text".to_string());
+        assert!(verifier
+            .verify("text".to_string(), signature.clone())
+            .is_ok());
+        assert!(verifier.verify("other".to_string(), signature).is_err());
+        assert!(verifier
+            .verify("text".to_string(), "zz".to_string())
+            .is_err());
+    }
+}
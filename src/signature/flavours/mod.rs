@@ -0,0 +1,15 @@
+use crate::{
+    curves::nist::{P256, Z256},
+    hash::sha256::Sha256,
+    signature::extensions::{make_dyn, string_signature, SignatureObject},
+};
+
+use self::ecdsa::Ecdsa;
+
+pub mod ecdsa;
+pub mod schnorr;
+
+/// Low-s ECDSA over P-256 with SHA-256.
+pub fn p256_ecdsa() -> SignatureObject {
+    make_dyn(string_signature(Ecdsa::<Z256, P256, Sha256>::new().low_s()))
+}
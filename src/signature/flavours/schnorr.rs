@@ -0,0 +1,296 @@
+//! Schnorr signatures over any group of known order.
+
+use std::marker::PhantomData;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::One;
+use rand::{Rng, RngCore};
+use thiserror::Error;
+
+use crate::{
+    algebra::{
+        sec1::field_len,
+        traits::{multi_scalar_mul, FinGroup},
+    },
+    bytes::{Deserialize, Either, Serialize},
+    hash::Digest,
+    signature::base::*,
+};
+
+pub struct Schnorr<F, D> {
+    pub get_group_generator: F,
+    pub hash: PhantomData<D>,
+}
+
+impl<F, D> From<F> for Schnorr<F, D> {
+    fn from(get_group_generator: F) -> Self {
+        Self {
+            get_group_generator,
+            hash: PhantomData,
+        }
+    }
+}
+
+pub struct SchnorrPublicKey<T, D> {
+    pub group_generator: T,
+    pub key: T,
+    hash: PhantomData<D>,
+}
+
+pub struct SchnorrSecret<T, D> {
+    pub public: SchnorrPublicKey<T, D>,
+    pub secret: BigInt,
+}
+
+/// Commitment `R = k * G` and response `s = k + e * x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrSignature<T> {
+    pub commitment: T,
+    pub response: BigUint,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SchnorrError {
+    #[error("response is out of range")]
+    OutOfRange,
+    #[error("signature does not match")]
+    Mismatch,
+    #[error("not enough bytes for the response")]
+    NotEnoughBytes,
+}
+
+impl<T: FinGroup + Serialize, D: Digest> SchnorrPublicKey<T, D> {
+    /// Fiat-Shamir challenge `H(R || P || m)` modulo the group order.
+    fn challenge(&self, commitment: &T, message: &[u8]) -> BigUint {
+        let mut hasher = D::new();
+        hasher
+            .update(&commitment.clone().serialize())
+            .update(&self.key.clone().serialize())
+            .update(message);
+        BigUint::from_bytes_be(&hasher.finalize()) % T::order()
+    }
+}
+
+impl<F, D, T> Sig for Schnorr<F, D>
+where
+    F: Fn(&mut dyn RngCore) -> T,
+    T: 'static,
+{
+    type Message = Vec<u8>;
+    type Signature = SchnorrSignature<T>;
+}
+
+impl<F, D, T> SignatureScheme for Schnorr<F, D>
+where
+    F: Fn(&mut dyn RngCore) -> T,
+    T: FinGroup + Serialize + 'static,
+    D: Digest + 'static,
+{
+    type Verifier = SchnorrPublicKey<T, D>;
+    type Signer = SchnorrSecret<T, D>;
+
+    fn generate_keys(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Verifier, Self::Signer) {
+        let group_generator = (self.get_group_generator)(rng);
+        let secret = rng.gen_range(BigInt::one()..T::order().into());
+        let key = group_generator.clone().mul_secret(&secret);
+        let public = || SchnorrPublicKey {
+            group_generator: group_generator.clone(),
+            key: key.clone(),
+            hash: PhantomData,
+        };
+        (
+            public(),
+            SchnorrSecret {
+                public: public(),
+                secret,
+            },
+        )
+    }
+}
+
+impl<T: 'static, D> Sig for SchnorrSecret<T, D> {
+    type Message = Vec<u8>;
+    type Signature = SchnorrSignature<T>;
+}
+
+impl<T, D> Signer for SchnorrSecret<T, D>
+where
+    T: FinGroup + Serialize + 'static,
+    D: Digest,
+{
+    fn sign(
+        &self,
+        rng: &mut dyn RngCore,
+        message: Self::Message,
+    ) -> Self::Signature {
+        let order = BigInt::from(T::order());
+        let nonce = rng.gen_range(BigInt::one()..order.clone());
+        let commitment = self.public.group_generator.clone().mul_secret(&nonce);
+        let challenge = self.public.challenge(&commitment, &message);
+        let response = (nonce + BigInt::from(challenge) * &self.secret) % order;
+        SchnorrSignature {
+            commitment,
+            response: response.magnitude().clone(),
+        }
+    }
+}
+
+impl<T: 'static, D> Sig for SchnorrPublicKey<T, D> {
+    type Message = Vec<u8>;
+    type Signature = SchnorrSignature<T>;
+}
+
+impl<T, D> Verifier for SchnorrPublicKey<T, D>
+where
+    T: FinGroup + Serialize + 'static,
+    D: Digest,
+{
+    type Error = SchnorrError;
+
+    /// Checks `s * G - e * P = R`.
+    fn verify(
+        &self,
+        message: Self::Message,
+        signature: Self::Signature,
+    ) -> Result<(), Self::Error> {
+        let SchnorrSignature {
+            commitment,
+            response,
+        } = signature;
+        if response >= T::order() {
+            return Err(SchnorrError::OutOfRange);
+        }
+        let challenge = self.challenge(&commitment, &message);
+        let check = multi_scalar_mul(
+            &[self.group_generator.clone(), self.key.clone()],
+            &[response.into(), -BigInt::from(challenge)],
+        );
+        if check == commitment {
+            Ok(())
+        } else {
+            Err(SchnorrError::Mismatch)
+        }
+    }
+}
+
+/// Commitment followed by the response in big-endian, padded to the byte
+/// length of the group order.
+impl<T: FinGroup + Serialize> Serialize for SchnorrSignature<T> {
+    fn serialize(self) -> Vec<u8> {
+        let len = field_len(&T::order());
+        let mut result = self.commitment.serialize();
+        let response = self.response.to_bytes_be();
+        result.resize(result.len() + len - response.len(), 0);
+        result.extend_from_slice(&response);
+        result
+    }
+}
+
+impl<T: FinGroup + Deserialize> Deserialize for SchnorrSignature<T> {
+    type Error = Either<T::Error, SchnorrError>;
+
+    fn deserialize(
+        stream: &mut impl Iterator<Item = u8>,
+    ) -> Result<Option<Self>, Self::Error> {
+        let commitment = match T::deserialize(stream).map_err(Either::Left)? {
+            Some(commitment) => commitment,
+            None => return Ok(None),
+        };
+        let len = field_len(&T::order());
+        let response: Vec<_> = stream.take(len).collect();
+        if response.len() < len {
+            return Err(Either::Right(SchnorrError::NotEnoughBytes));
+        }
+        Ok(Some(Self {
+            commitment,
+            response: BigUint::from_bytes_be(&response),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use rand::{thread_rng, RngCore};
+
+    use super::{Schnorr, SchnorrError};
+    use crate::{
+        algebra::{
+            curve::{Curve, EllipticPoint},
+            traits::FinGroup,
+            zp::Generator,
+        },
+        bytes::{consume_deser, Deserialize, Serialize},
+        dlies::{FFDHE2048, Z2048},
+        ecges::{generator, Z224Mont, P224},
+        hash::sha256::Sha256,
+        signature::{
+            base::{SignatureScheme, Signer, Verifier},
+            extensions::{make_dyn, string_signature},
+        },
+    };
+
+    fn check<T>(group_generator: T)
+    where
+        T: FinGroup + Serialize + Deserialize + 'static,
+    {
+        let scheme = Schnorr::<_, Sha256>::from(move |_: &mut dyn RngCore| {
+            group_generator.clone()
+        });
+        let mut rng = thread_rng();
+        let (verifier, signer) = scheme.generate_keys(&mut rng);
+        let message = b"schnorr".to_vec();
+        let signature = signer.sign(&mut rng, message.clone());
+        assert_eq!(verifier.verify(message.clone(), signature.clone()), Ok(()));
+        assert_eq!(
+            verifier.verify(b"other".to_vec(), signature.clone()),
+            Err(SchnorrError::Mismatch)
+        );
+        let mut forged = signature.clone();
+        forged.response = (forged.response + 1u32) % T::order();
+        assert_eq!(
+            verifier.verify(message.clone(), forged),
+            Err(SchnorrError::Mismatch)
+        );
+        let mut forged = signature.clone();
+        forged.response = T::order();
+        assert_eq!(
+            verifier.verify(message.clone(), forged),
+            Err(SchnorrError::OutOfRange)
+        );
+        let bytes = signature.clone().serialize();
+        let parsed = consume_deser(bytes).ok().unwrap();
+        assert_eq!(verifier.verify(message, parsed), Ok(()));
+    }
+
+    #[test]
+    fn zp() {
+        check(Z2048::from(FFDHE2048::generator()));
+    }
+
+    #[test]
+    fn p224() {
+        let (x, y) = generator().into();
+        let (x, y) = (BigUint::from(x), BigUint::from(y));
+        let point: EllipticPoint<Z224Mont, P224> =
+            P224::affine(x.into(), y.into()).unwrap();
+        check(point);
+    }
+
+    #[test]
+    fn strings() {
+        let scheme = make_dyn(string_signature(Schnorr::<_, Sha256>::from(
+            |_: &mut dyn RngCore| generator(),
+        )));
+        let mut rng = thread_rng();
+        let (verifier, signer) = scheme.generate_keys(&mut rng);
+        let signature = signer.sign(&mut rng, "text".to_string());
+        assert!(verifier
+            .verify("text".to_string(), signature.clone())
+            .is_ok());
+        assert!(verifier.verify("txet".to_string(), signature).is_err());
+    }
+}
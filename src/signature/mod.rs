@@ -0,0 +1,4 @@
+pub mod base;
+pub mod extensions;
+pub mod flavours;
+pub mod rfc6979;
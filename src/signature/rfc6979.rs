@@ -0,0 +1,131 @@
+//! Deterministic nonces from RFC 6979, section 3.2.
+
+use std::marker::PhantomData;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::hash::{hmac::hmac, Digest};
+
+/// Leftmost `qlen` bits of `data` as an integer.
+pub fn bits2int(data: &[u8], qlen: u64) -> BigUint {
+    let value = BigUint::from_bytes_be(data);
+    let len = data.len() as u64 * 8;
+    if len > qlen {
+        value >> (len - qlen)
+    } else {
+        value
+    }
+}
+
+/// Big-endian encoding of `value` padded to the byte length of `order`.
+pub fn int2octets(value: &BigUint, order: &BigUint) -> Vec<u8> {
+    let len = (order.bits() as usize).div_ceil(8);
+    let bytes = value.to_bytes_be();
+    let mut result = vec![0; len.saturating_sub(bytes.len())];
+    result.extend_from_slice(&bytes);
+    result
+}
+
+fn bits2octets(data: &[u8], order: &BigUint) -> Vec<u8> {
+    int2octets(&(bits2int(data, order.bits()) % order), order)
+}
+
+/// HMAC-DRBG yielding candidate nonces in `[1, order)`.
+///
+/// The first item is the nonce of the RFC; the following ones are used only
+/// if the signer has to reject it.
+pub struct Nonces<D> {
+    key: Vec<u8>,
+    value: Vec<u8>,
+    order: BigUint,
+    started: bool,
+    hash: PhantomData<D>,
+}
+
+impl<D: Digest> Nonces<D> {
+    pub fn new(secret: &BigUint, digest: &[u8], order: &BigUint) -> Self {
+        let mut result = Self {
+            key: vec![0; D::OUTPUT_LEN],
+            value: vec![1; D::OUTPUT_LEN],
+            order: order.clone(),
+            started: false,
+            hash: PhantomData,
+        };
+        let seed =
+            [int2octets(secret, order), bits2octets(digest, order)].concat();
+        result.reseed(&[&[0], &seed[..]].concat());
+        result.reseed(&[&[1], &seed[..]].concat());
+        result
+    }
+
+    fn reseed(&mut self, data: &[u8]) {
+        self.key = hmac::<D>(&self.key, &[&self.value[..], data].concat());
+        self.value = hmac::<D>(&self.key, &self.value);
+    }
+}
+
+impl<D: Digest> Iterator for Nonces<D> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let qlen = self.order.bits();
+        loop {
+            if self.started {
+                self.reseed(&[0]);
+            }
+            self.started = true;
+            let mut t = Vec::new();
+            while (t.len() as u64) * 8 < qlen {
+                self.value = hmac::<D>(&self.key, &self.value);
+                t.extend_from_slice(&self.value);
+            }
+            let k = bits2int(&t, qlen);
+            if !k.is_zero() && k < self.order {
+                return Some(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::{bits2int, int2octets, Nonces};
+    use crate::{
+        ecges::hex,
+        hash::{sha256::Sha256, Digest},
+    };
+
+    /// Appendix A.1 of RFC 6979.
+    #[test]
+    fn appendix_a1() {
+        let order = hex("4000000000000000000020108A2E0CC0D99F8A5EF");
+        let secret = hex("09A4D6792295A7F730FC3F2B49CBC0F62E862272F");
+        let digest = Sha256::digest(b"sample");
+        assert_eq!(
+            bits2int(&digest, order.bits()),
+            hex("5795EDF0D54DB760F156F0EB4A7A0FE38D418E813")
+        );
+        assert_eq!(
+            hex::encode(int2octets(&secret, &order)),
+            "009a4d6792295a7f730fc3f2b49cbc0f62e862272f"
+        );
+        let k = Nonces::<Sha256>::new(&secret, &digest, &order).next();
+        assert_eq!(k, Some(hex("23AF4074C90A02B3FE61D286D5C87F425E6BDD81B")));
+    }
+
+    #[test]
+    fn retries() {
+        let order = BigUint::from(0xffu32);
+        let nonces: Vec<_> =
+            Nonces::<Sha256>::new(&BigUint::from(7u32), b"m", &order)
+                .take(5)
+                .collect();
+        assert!(nonces
+            .iter()
+            .all(|k| k > &BigUint::from(0u32) && k < &order));
+        assert!(nonces.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}